@@ -0,0 +1,236 @@
+use std::collections::HashMap;
+use std::iter::FusedIterator;
+use std::rc::Rc;
+
+use crate::{Event, Limit, ParserError, Span};
+
+/// Iterator adapter that replaces aliases with the events of their anchored
+/// nodes.
+///
+/// The expander consumes events paired with their spans, as produced by
+/// [`ParserSpannedIter`], and can feed a [`Loader`].  Replayed events carry
+/// the span of the alias and no anchors.  Expansion is subject to a budget:
+/// by default, the expander fails with [`ParserError::LimitExceeded`] once it
+/// produces more than 100 times as many events as it consumes.  The iterator
+/// is fused, and produces `None` forever after the end of stream or after a
+/// first encountered error.
+///
+/// ```
+/// # use libyaml::*;
+/// #
+/// # fn doctest() -> Result<(), ParserError> {
+/// let parser = Parser::from_str("[&a [x, y], *a]")?;
+/// let events = AliasExpander::new(parser.into_spanned_iter()).collect::<Result<Vec<_>, _>>()?;
+/// assert_eq!(events.iter().filter(|(e, _)| e.kind() == EventKind::Scalar).count(), 4);
+///
+/// let bomb = "[&a [x, x, x, x, x, x, x, x], &b [*a, *a, *a, *a, *a, *a, *a, *a], [*b, *b, *b, *b, *b, *b, *b, *b]]";
+/// let parser = Parser::from_str(bomb)?;
+/// let result = AliasExpander::new(parser.into_spanned_iter()).max_ratio(10).collect::<Result<Vec<_>, _>>();
+/// assert!(matches!(result, Err(ParserError::LimitExceeded { limit: Limit::ExpansionRatio, .. })));
+/// # Ok(())
+/// # }
+/// # doctest().unwrap();
+/// ```
+///
+/// [`ParserSpannedIter`]: struct.ParserSpannedIter.html
+/// [`Loader`]: struct.Loader.html
+/// [`ParserError::LimitExceeded`]: enum.ParserError.html#variant.LimitExceeded
+pub struct AliasExpander<I> {
+    events: I,
+    anchors: HashMap<String, Rc<[Event]>>,
+    recordings: Vec<Recording>,
+    replay: Option<(Rc<[Event]>, usize, Span)>,
+    max_events: Option<usize>,
+    max_ratio: Option<usize>,
+    consumed: usize,
+    produced: usize,
+    fuse_burnt: bool,
+}
+
+struct Recording {
+    anchor: String,
+    events: Vec<Event>,
+    depth: usize,
+}
+
+impl<I> AliasExpander<I>
+where
+    I: Iterator<Item = Result<(Event, Span), ParserError>>,
+{
+    /// Create an expander consuming the given events.
+    pub fn new(events: I) -> Self {
+        Self {
+            events,
+            anchors: HashMap::new(),
+            recordings: Vec::new(),
+            replay: None,
+            max_events: None,
+            max_ratio: Some(100),
+            consumed: 0,
+            produced: 0,
+            fuse_burnt: false,
+        }
+    }
+
+    /// Set the maximum number of events produced, including replayed events.
+    /// Unlimited by default.
+    pub fn max_events(mut self, max_events: usize) -> Self {
+        self.max_events = Some(max_events);
+        self
+    }
+
+    /// Set the maximum ratio of produced events to consumed events, or remove
+    /// the limit with `None`.  Defaults to 100.
+    pub fn max_ratio(mut self, max_ratio: impl Into<Option<usize>>) -> Self {
+        self.max_ratio = max_ratio.into();
+        self
+    }
+
+    fn next_event(&mut self) -> Result<Option<(Event, Span)>, ParserError> {
+        let (event, span) = match self.next_replayed() {
+            Some(replayed) => {
+                replayed
+            },
+            None => {
+                match self.events.next().transpose()? {
+                    Some((Event::Alias { anchor }, span)) => {
+                        self.consumed += 1;
+
+                        match self.anchors.get(&anchor) {
+                            Some(events) => self.replay = Some((events.clone(), 0, span)),
+                            None => return Err(ParserError::UnknownAnchor { anchor, mark: span.start }),
+                        }
+
+                        self.next_replayed().expect("recorded node without events")
+                    },
+                    Some((event, span)) => {
+                        self.consumed += 1;
+
+                        if let Event::DocumentStart { .. } = event {
+                            self.anchors.clear();
+                        }
+
+                        (event, span)
+                    },
+                    None => {
+                        return Ok(None);
+                    },
+                }
+            },
+        };
+
+        self.produced += 1;
+
+        if self.max_events.is_some_and(|max| self.produced > max) {
+            return Err(ParserError::LimitExceeded { limit: Limit::ExpandedEvents, mark: span.start });
+        }
+
+        if self.max_ratio.is_some_and(|ratio| self.produced > self.consumed.saturating_mul(ratio)) {
+            return Err(ParserError::LimitExceeded { limit: Limit::ExpansionRatio, mark: span.start });
+        }
+
+        self.record(&event);
+
+        Ok(Some((event, span)))
+    }
+
+    fn next_replayed(&mut self) -> Option<(Event, Span)> {
+        let (events, index, span) = self.replay.as_mut()?;
+        let event = events[*index].clone();
+        let span = *span;
+
+        *index += 1;
+
+        if *index == events.len() {
+            self.replay = None;
+        }
+
+        Some((event, span))
+    }
+
+    fn record(&mut self, event: &Event) {
+        let (anchor, opens) = match event {
+            Event::Scalar { anchor, .. } => (anchor, false),
+            Event::SequenceStart { anchor, .. } | Event::MappingStart { anchor, .. } => (anchor, true),
+            _ => (&None, false),
+        };
+
+        let depth_change = match event {
+            Event::SequenceStart { .. } | Event::MappingStart { .. } => 1,
+            Event::SequenceEnd | Event::MappingEnd => -1,
+            _ => 0,
+        };
+
+        if !self.recordings.is_empty() || anchor.is_some() {
+            let unanchored = without_anchor(event.clone());
+
+            for recording in &mut self.recordings {
+                recording.events.push(unanchored.clone());
+                recording.depth = recording.depth.wrapping_add_signed(depth_change);
+            }
+
+            while self.recordings.last().is_some_and(|recording| recording.depth == 0) {
+                let recording = self.recordings.pop().unwrap();
+                self.anchors.insert(recording.anchor, recording.events.into());
+            }
+
+            if let Some(anchor) = anchor {
+                if opens {
+                    self.recordings.push(Recording { anchor: anchor.clone(), events: vec![unanchored], depth: 1 });
+                } else {
+                    self.anchors.insert(anchor.clone(), Rc::from(vec![unanchored]));
+                }
+            }
+        }
+    }
+}
+
+impl<I> Iterator for AliasExpander<I>
+where
+    I: Iterator<Item = Result<(Event, Span), ParserError>>,
+{
+    type Item = Result<(Event, Span), ParserError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.fuse_burnt {
+            None
+        } else {
+            match self.next_event() {
+                Ok(Some(event)) => {
+                    Some(Ok(event))
+                },
+                Ok(None) => {
+                    self.fuse_burnt = true;
+                    None
+                },
+                Err(e) => {
+                    self.fuse_burnt = true;
+                    Some(Err(e))
+                },
+            }
+        }
+    }
+}
+
+impl<I> FusedIterator for AliasExpander<I>
+where
+    I: Iterator<Item = Result<(Event, Span), ParserError>>,
+{
+}
+
+fn without_anchor(event: Event) -> Event {
+    match event {
+        Event::Scalar { tag, value, plain_implicit, quoted_implicit, style, .. } => {
+            Event::Scalar { anchor: None, tag, value, plain_implicit, quoted_implicit, style }
+        },
+        Event::SequenceStart { tag, implicit, style, .. } => {
+            Event::SequenceStart { anchor: None, tag, implicit, style }
+        },
+        Event::MappingStart { tag, implicit, style, .. } => {
+            Event::MappingStart { anchor: None, tag, implicit, style }
+        },
+        event => {
+            event
+        },
+    }
+}
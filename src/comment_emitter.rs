@@ -0,0 +1,204 @@
+use std::cell::RefCell;
+use std::collections::HashSet;
+use std::io;
+use std::mem;
+use std::rc::Rc;
+
+use crate::{Comments, Emitter, EmitterError, Event, Parser, ParserError, Span};
+use crate::shared_writer::SharedWriter;
+
+/// Emitter wrapper that reinserts comments and blank lines into the output.
+///
+/// Each event is emitted together with its [`Comments`], as produced by
+/// [`CommentExtractor`].  The output of a stream is held back until its
+/// *STREAM-END* event; it is then parsed again, and the comments are inserted
+/// at the equivalent positions: lines before the line of their event, at its
+/// indentation, and trailing comments at the end of the line where their
+/// event ends, or at the end of the header line of a block scalar.  A
+/// trailing comment that cannot be placed there, for example because another
+/// trailing comment already ends the line, is moved to the lines before its
+/// event.
+///
+/// Only comments and blank lines are carried over from the source, not its
+/// layout.  Line breaks follow the configuration of the wrapped emitter, set
+/// with [`EmitterBuilder::line_break`], so a source with CRLF line breaks
+/// comes back with LF unless the emitter is configured otherwise.  A flow
+/// collection the emitter writes on one line takes the comments from inside
+/// it to the end of that line: `a: [1, # one` followed by `2]` comes back as
+/// `a: [1, 2] # one`.
+///
+/// Nothing reaches the wrapped writer before *STREAM-END* has been emitted.
+/// Output of a stream that is never ended, because the emitter is dropped
+/// early or an error occurs, is discarded.
+///
+/// ```
+/// # use libyaml::*;
+/// #
+/// # fn doctest() -> Result<(), Box<dyn std::error::Error>> {
+/// let source = "\
+/// ## Server settings
+/// server:
+///   host: localhost # or an address
+///
+///   ## Privileged ports need root
+///   port: 8080
+/// ";
+///
+/// let mut output = Vec::new();
+/// let mut emitter = CommentEmitter::new(Emitter::new(&mut output)?);
+///
+/// for event in CommentExtractor::new(source, Parser::from_str(source)?.into_spanned_iter()) {
+///     let (event, _, comments) = event?;
+///     emitter.emit(event, comments)?;
+/// }
+///
+/// drop(emitter);
+/// assert_eq!(String::from_utf8(output)?, source);
+///
+/// let source = "a: 1 # one\r\n\r\nb: 2\r\n";
+/// let mut output = Vec::new();
+/// let emitter = EmitterBuilder::new(&mut output)?.line_break(LineBreak::CrLn).finish();
+/// let mut emitter = CommentEmitter::new(emitter);
+///
+/// for event in CommentExtractor::new(source, Parser::from_str(source)?.into_spanned_iter()) {
+///     let (event, _, comments) = event?;
+///     emitter.emit(event, comments)?;
+/// }
+///
+/// drop(emitter);
+/// assert_eq!(String::from_utf8(output)?, source);
+/// # Ok(())
+/// # }
+/// # doctest().unwrap();
+/// ```
+///
+/// [`Comments`]: struct.Comments.html
+/// [`CommentExtractor`]: struct.CommentExtractor.html
+/// [`EmitterBuilder::line_break`]: struct.EmitterBuilder.html#method.line_break
+pub struct CommentEmitter<'a> {
+    emitter: Box<Emitter<'a>>,
+    buffer: Rc<RefCell<Vec<u8>>>,
+    writer: Box<dyn io::Write + 'a>,
+    comments: Vec<Comments>,
+}
+
+impl<'a> CommentEmitter<'a> {
+    /// Wrap an emitter that has not emitted any event yet.  The configuration
+    /// of the emitter is kept, and its writer only receives output once a
+    /// stream ends.
+    pub fn new(mut emitter: Box<Emitter<'a>>) -> Self {
+        let buffer = Rc::new(RefCell::new(Vec::new()));
+        let writer = emitter.replace_writer(Box::new(SharedWriter(buffer.clone())));
+        Self { emitter, buffer, writer, comments: Vec::new() }
+    }
+
+    /// Emit an event with its comments.
+    ///
+    /// The output is held back until the *STREAM-END* event, which writes the
+    /// whole stream with its comments; if that fails, the stream is lost.
+    pub fn emit(&mut self, event: Event, comments: Comments) -> Result<(), EmitterError> {
+        let stream_end = matches!(event, Event::StreamEnd);
+
+        self.emitter.emit(event)?;
+        self.comments.push(comments);
+
+        if stream_end {
+            self.emitter.flush()?;
+
+            let output = mem::take(&mut *self.buffer.borrow_mut());
+            let comments = mem::take(&mut self.comments);
+            let output = String::from_utf8(output).map_err(invalid_data)?;
+            let output = insert_comments(&output, &comments).map_err(invalid_data)?;
+
+            self.writer.write_all(output.as_bytes()).map_err(EmitterError::IoError)?;
+        }
+
+        Ok(())
+    }
+}
+
+fn invalid_data<E>(error: E) -> EmitterError
+where
+    E: Into<Box<dyn std::error::Error + Send + Sync>>,
+{
+    EmitterError::IoError(io::Error::new(io::ErrorKind::InvalidData, error))
+}
+
+fn insert_comments(yaml: &str, comments: &[Comments]) -> Result<String, ParserError> {
+    let spans = Parser::from_str(yaml)?
+        .into_spanned_iter()
+        .map(|event| event.map(|(_, span)| span))
+        .collect::<Result<Vec<Span>, _>>()?;
+
+    // Inserted lines follow the line break configured on the emitter.
+    let line_break = match yaml.find(['\r', '\n']) {
+        Some(i) if yaml[i..].starts_with("\r\n") => "\r\n",
+        Some(i) if yaml[i..].starts_with('\r') => "\r",
+        _ => "\n",
+    };
+
+    let line_start = |pos: usize| yaml[..pos].rfind(line_break).map_or(0, |i| i + line_break.len());
+    let line_end = |pos: usize| yaml[pos..].find(line_break).map_or(yaml.len(), |i| pos + i);
+
+    let mut insertions = Vec::new();
+    let mut trailing_lines = HashSet::new();
+
+    for (i, (span, comments)) in spans.iter().zip(comments).enumerate() {
+        let mut before = comments.before.iter().map(String::as_str).collect::<Vec<_>>();
+
+        if let Some(trailing) = &comments.trailing {
+            let block_scalar = span.end.column == 0 && span.start.index < span.end.index;
+            let end = line_end(if block_scalar { span.start.index } else { span.end.index });
+            let inside_span = spans[i + 1..]
+                .iter()
+                .take_while(|next| next.start.index < end)
+                .any(|next| next.end.index > end);
+
+            if (span.end.column == 0 && !block_scalar) || inside_span || !trailing_lines.insert(end) {
+                before.push(trailing);
+            } else {
+                insertions.push((end, format!(" {}", trailing)));
+            }
+        }
+
+        if before.is_empty() {
+            continue;
+        }
+
+        let mut start = line_start(span.start.index);
+
+        for previous in spans[..i].iter().rev() {
+            if previous.end.index <= start {
+                break;
+            }
+
+            if previous.start.index < start {
+                start = line_start(previous.start.index);
+            }
+        }
+
+        let indent = yaml[start..].len() - yaml[start..].trim_start_matches(' ').len();
+
+        for line in before {
+            if line.is_empty() {
+                insertions.push((start, String::from(line_break)));
+            } else {
+                insertions.push((start, format!("{:indent$}{}{}", "", line, line_break, indent = indent)));
+            }
+        }
+    }
+
+    insertions.sort_by_key(|&(pos, _)| pos);
+
+    let mut output = String::with_capacity(yaml.len() + insertions.iter().map(|(_, s)| s.len()).sum::<usize>());
+    let mut copied = 0;
+
+    for (pos, insertion) in insertions {
+        output.push_str(&yaml[copied..pos]);
+        output.push_str(&insertion);
+        copied = pos;
+    }
+
+    output.push_str(&yaml[copied..]);
+    Ok(output)
+}
@@ -0,0 +1,148 @@
+use std::iter::FusedIterator;
+use std::mem;
+
+use crate::{Comments, Event, ParserError, ScalarStyle, Span};
+
+/// Iterator adapter that attaches the comments and blank lines of the source
+/// to events.
+///
+/// LibYAML drops comments, so the extractor re-scans the source between the
+/// spans of consecutive events.  It consumes events paired with their spans,
+/// as produced by [`ParserSpannedIter`] for the same source.  A comment on the
+/// line where an event ends, or on the header line of a block scalar, is its
+/// trailing comment.  Whole comment lines and blank lines are attached to the
+/// next event that occupies some text, such as a key or an item, rather than
+/// to an implicit document start or end, or to the start or end of a block
+/// collection.  The iterator is fused, and produces `None` forever after the
+/// end of stream or after a first encountered error.
+///
+/// ```
+/// # use libyaml::*;
+/// #
+/// # fn doctest() -> Result<(), ParserError> {
+/// let source = "# greeting\nhello: world  # planet\n";
+/// let parser = Parser::from_str(source)?;
+/// let events = CommentExtractor::new(source, parser.into_spanned_iter()).collect::<Result<Vec<_>, _>>()?;
+///
+/// assert_eq!(events[3].2.before, ["# greeting"]);
+/// assert_eq!(events[4].2.trailing.as_deref(), Some("# planet"));
+/// # Ok(())
+/// # }
+/// # doctest().unwrap();
+/// ```
+///
+/// [`ParserSpannedIter`]: struct.ParserSpannedIter.html
+pub struct CommentExtractor<'s, I> {
+    source: &'s str,
+    events: I,
+    pending: Option<(Event, Span, Comments)>,
+    carried: Vec<String>,
+    fuse_burnt: bool,
+}
+
+impl<'s, I> CommentExtractor<'s, I>
+where
+    I: Iterator<Item = Result<(Event, Span), ParserError>>,
+{
+    /// Create an extractor for the events parsed from the given source.
+    pub fn new(source: &'s str, events: I) -> Self {
+        Self {
+            source: source.strip_prefix('\u{feff}').unwrap_or(source),
+            events,
+            pending: None,
+            carried: Vec::new(),
+            fuse_burnt: false,
+        }
+    }
+
+    fn next_event(&mut self) -> Result<Option<(Event, Span, Comments)>, ParserError> {
+        loop {
+            let (event, span) = match self.events.next().transpose()? {
+                Some(event) => event,
+                None => return Ok(self.pending.take()),
+            };
+
+            if let Some((_, previous, comments)) = &mut self.pending {
+                let gap = self.source.get(previous.end.index..span.start.index).unwrap_or("");
+                let starts_line = previous.end.column == 0;
+                let segments = gap.split('\n').collect::<Vec<_>>();
+
+                for (i, segment) in segments.iter().enumerate() {
+                    let comment = find_comment(segment);
+
+                    if i == 0 && !starts_line {
+                        if comment.is_some() {
+                            comments.trailing = comment;
+                        }
+                    } else if let Some(comment) = comment {
+                        self.carried.push(comment);
+                    } else if i + 1 < segments.len() && segment.trim().is_empty() {
+                        self.carried.push(String::new());
+                    }
+                }
+            }
+
+            let passes_comments = span.start.index == span.end.index
+                && !matches!(event, Event::Scalar { .. } | Event::StreamEnd);
+
+            let comments = Comments {
+                before: if passes_comments { Vec::new() } else { mem::take(&mut self.carried) },
+                trailing: match event {
+                    Event::Scalar { style: Some(ScalarStyle::Literal), .. }
+                    | Event::Scalar { style: Some(ScalarStyle::Folded), .. } => {
+                        let header = self.source.get(span.start.index..span.end.index).unwrap_or("");
+                        find_comment(header.split('\n').next().unwrap_or(""))
+                    },
+                    _ => {
+                        None
+                    },
+                },
+            };
+
+            if let Some(ready) = self.pending.replace((event, span, comments)) {
+                return Ok(Some(ready));
+            }
+        }
+    }
+}
+
+impl<I> Iterator for CommentExtractor<'_, I>
+where
+    I: Iterator<Item = Result<(Event, Span), ParserError>>,
+{
+    type Item = Result<(Event, Span, Comments), ParserError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.fuse_burnt {
+            None
+        } else {
+            match self.next_event() {
+                Ok(Some(event)) => {
+                    Some(Ok(event))
+                },
+                Ok(None) => {
+                    self.fuse_burnt = true;
+                    None
+                },
+                Err(e) => {
+                    self.fuse_burnt = true;
+                    Some(Err(e))
+                },
+            }
+        }
+    }
+}
+
+impl<I> FusedIterator for CommentExtractor<'_, I>
+where
+    I: Iterator<Item = Result<(Event, Span), ParserError>>,
+{
+}
+
+fn find_comment(segment: &str) -> Option<String> {
+    let bytes = segment.as_bytes();
+
+    (0..bytes.len())
+        .find(|&i| bytes[i] == b'#' && (i == 0 || bytes[i - 1] == b' ' || bytes[i - 1] == b'\t'))
+        .map(|i| segment[i..].trim_end().to_owned())
+}
@@ -0,0 +1,23 @@
+/// Comments and blank lines attached to an event.
+///
+/// Produced by [`CommentExtractor`] and consumed by [`CommentEmitter`].
+///
+/// [`CommentExtractor`]: struct.CommentExtractor.html
+/// [`CommentEmitter`]: struct.CommentEmitter.html
+#[derive(Clone, Debug, Default, Eq, Hash, PartialEq)]
+pub struct Comments {
+    /// Lines preceding the event, each either a comment including its leading
+    /// `#`, or an empty string for a blank line.
+    pub before: Vec<String>,
+
+    /// Comment following the event on the same line, including its leading
+    /// `#`.
+    pub trailing: Option<String>,
+}
+
+impl Comments {
+    /// Check whether there are no comments or blank lines.
+    pub fn is_empty(&self) -> bool {
+        self.before.is_empty() && self.trailing.is_none()
+    }
+}
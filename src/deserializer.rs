@@ -0,0 +1,654 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::io;
+
+use serde::de::{self, DeserializeOwned, IntoDeserializer};
+
+use crate::{DeserializerError, Event, Limit, Mark, Parser, ParserError, ScalarStyle, Span};
+use crate::tag::{self, Schema};
+
+const RECURSION_LIMIT: usize = 128;
+
+/// Deserialize a value of type `T` from a stream containing one document.
+///
+/// ```
+/// # use serde::Deserialize;
+/// #
+/// #[derive(Deserialize)]
+/// struct Config {
+///     name: String,
+///     retries: u32,
+/// }
+///
+/// let config: Config = libyaml::from_reader(&b"name: foo\nretries: 3\n"[..]).unwrap();
+/// assert_eq!(config.name, "foo");
+/// assert_eq!(config.retries, 3);
+/// ```
+pub fn from_reader<R: io::Read, T: DeserializeOwned>(reader: R) -> Result<T, DeserializerError> {
+    Deserializer::new(Parser::new(reader)?).deserialize_single()
+}
+
+/// Deserialize a value of type `T` from a string containing one document.
+pub fn from_str<T: DeserializeOwned>(s: &str) -> Result<T, DeserializerError> {
+    Deserializer::new(Parser::from_str(s)?).deserialize_single()
+}
+
+/// Deserialize a value of type `T` from bytes containing one document.
+pub fn from_slice<T: DeserializeOwned>(v: &[u8]) -> Result<T, DeserializerError> {
+    Deserializer::new(Parser::from_slice(v)?).deserialize_single()
+}
+
+/// Serde deserializer driven by [`Parser`] events.
+///
+/// Every call to [`deserialize_document`] deserializes the next document of
+/// the stream.  Untagged plain scalars are resolved according to the YAML 1.2
+/// core schema; aliases are replaced with the events of the anchored node.
+/// Expansion is subject to the same budget as in [`AliasExpander`]: by
+/// default, the deserializer fails with [`ParserError::LimitExceeded`] once
+/// it produces more than 100 times as many events as it parses.
+///
+/// ```
+/// # use libyaml::*;
+/// #
+/// let bomb = "\
+/// a: &a [x, x, x, x, x, x, x, x, x, x]
+/// b: &b [*a, *a, *a, *a, *a, *a, *a, *a, *a, *a]
+/// c: &c [*b, *b, *b, *b, *b, *b, *b, *b, *b, *b]
+/// d: &d [*c, *c, *c, *c, *c, *c, *c, *c, *c, *c]
+/// e: &e [*d, *d, *d, *d, *d, *d, *d, *d, *d, *d]
+/// f: &f [*e, *e, *e, *e, *e, *e, *e, *e, *e, *e]
+/// g: &g [*f, *f, *f, *f, *f, *f, *f, *f, *f, *f]
+/// h: &h [*g, *g, *g, *g, *g, *g, *g, *g, *g, *g]
+/// i: &i [*h, *h, *h, *h, *h, *h, *h, *h, *h, *h]
+/// ";
+///
+/// let result = from_str::<serde::de::IgnoredAny>(bomb);
+/// match result {
+///     Err(DeserializerError::ParserError(error)) => {
+///         assert!(matches!(*error, ParserError::LimitExceeded { limit: Limit::ExpansionRatio, .. }));
+///     },
+///     result => panic!("expected an expansion ratio error, got {:?}", result),
+/// }
+/// ```
+///
+/// [`Parser`]: struct.Parser.html
+/// [`deserialize_document`]: #method.deserialize_document
+/// [`AliasExpander`]: struct.AliasExpander.html
+/// [`ParserError::LimitExceeded`]: enum.ParserError.html#variant.LimitExceeded
+pub struct Deserializer<'a> {
+    parser: Box<Parser<'a>>,
+    peeked: Option<(Event, Span)>,
+    replay: Vec<(Event, Span)>,
+    anchors: HashMap<String, Vec<(Event, Span)>>,
+    recordings: Vec<Recording>,
+    max_events: Option<usize>,
+    max_ratio: Option<usize>,
+    consumed: usize,
+    produced: usize,
+    remaining_depth: usize,
+    finished: bool,
+}
+
+struct Recording {
+    anchor: String,
+    events: Vec<(Event, Span)>,
+    depth: usize,
+}
+
+impl<'a> Deserializer<'a> {
+    /// Create a deserializer reading events from the given parser.
+    pub fn new(parser: Box<Parser<'a>>) -> Self {
+        Self {
+            parser,
+            peeked: None,
+            replay: Vec::new(),
+            anchors: HashMap::new(),
+            recordings: Vec::new(),
+            max_events: None,
+            max_ratio: Some(100),
+            consumed: 0,
+            produced: 0,
+            remaining_depth: RECURSION_LIMIT,
+            finished: false,
+        }
+    }
+
+    /// Set the maximum number of events deserialized, including replayed
+    /// events.  Unlimited by default.
+    pub fn max_events(mut self, max_events: usize) -> Self {
+        self.max_events = Some(max_events);
+        self
+    }
+
+    /// Set the maximum ratio of deserialized events to parsed events, or
+    /// remove the limit with `None`.  Defaults to 100.
+    pub fn max_ratio(mut self, max_ratio: impl Into<Option<usize>>) -> Self {
+        self.max_ratio = max_ratio.into();
+        self
+    }
+
+    /// Deserialize the next document of the stream; returns `None` after the
+    /// end of stream.
+    pub fn deserialize_document<T: DeserializeOwned>(&mut self) -> Result<Option<T>, DeserializerError> {
+        loop {
+            if self.finished {
+                return Ok(None);
+            }
+
+            match self.next()? {
+                (Event::StreamStart { .. }, _) => {
+                    continue;
+                },
+                (Event::DocumentStart { .. }, _) => {
+                    break;
+                },
+                (Event::StreamEnd, _) => {
+                    self.finished = true;
+                    return Ok(None);
+                },
+                (event, span) => {
+                    return Err(unexpected(&event, span));
+                },
+            }
+        }
+
+        let value = T::deserialize(&mut *self)?;
+
+        match self.next()? {
+            (Event::DocumentEnd { .. }, _) => {
+                self.anchors.clear();
+                Ok(Some(value))
+            },
+            (event, span) => {
+                Err(unexpected(&event, span))
+            },
+        }
+    }
+
+    fn deserialize_single<T: DeserializeOwned>(mut self) -> Result<T, DeserializerError> {
+        let value = match self.deserialize_document()? {
+            Some(value) => value,
+            None => return T::deserialize(NullDeserializer),
+        };
+
+        match self.next()? {
+            (Event::StreamEnd, _) => Ok(value),
+            (_, span) => Err(error("expected a single YAML document", span.start)),
+        }
+    }
+
+    fn next(&mut self) -> Result<(Event, Span), DeserializerError> {
+        if let Some(event) = self.peeked.take() {
+            return Ok(event);
+        }
+
+        loop {
+            let (event, span, replayed) = match self.replay.pop() {
+                Some((event, span)) => (event, span, true),
+                None => {
+                    let (event, span) = self.parser.parse_spanned()?;
+                    self.consumed += 1;
+                    (event, span, false)
+                },
+            };
+
+            if let Event::StreamEnd = event {
+                self.finished = true;
+            }
+
+            if let Event::Alias { anchor } = &event {
+                match self.anchors.get(anchor) {
+                    Some(events) => {
+                        let produced = self.produced.saturating_add(events.len());
+
+                        if self.max_events.is_some_and(|max| produced > max) {
+                            return Err(ParserError::LimitExceeded { limit: Limit::ExpandedEvents, mark: span.start }.into());
+                        }
+
+                        if self.max_ratio.is_some_and(|ratio| produced > self.consumed.saturating_mul(ratio)) {
+                            return Err(ParserError::LimitExceeded { limit: Limit::ExpansionRatio, mark: span.start }.into());
+                        }
+
+                        self.replay.extend(events.iter().rev().cloned());
+                        continue;
+                    },
+                    None => {
+                        return Err(error(format_args!("unknown anchor `{}`", anchor), span.start));
+                    },
+                }
+            }
+
+            self.produced += 1;
+            self.record(&event, span, replayed);
+            return Ok((event, span));
+        }
+    }
+
+    fn peek(&mut self) -> Result<&(Event, Span), DeserializerError> {
+        if self.peeked.is_none() {
+            self.peeked = Some(self.next()?);
+        }
+
+        Ok(self.peeked.as_ref().unwrap())
+    }
+
+    fn record(&mut self, event: &Event, span: Span, replayed: bool) {
+        if !replayed {
+            let anchor = match event {
+                Event::Scalar { anchor: Some(anchor), .. } => Some(anchor),
+                Event::SequenceStart { anchor: Some(anchor), .. } => Some(anchor),
+                Event::MappingStart { anchor: Some(anchor), .. } => Some(anchor),
+                _ => None,
+            };
+
+            if let Some(anchor) = anchor {
+                self.recordings.push(Recording { anchor: anchor.clone(), events: Vec::new(), depth: 0 });
+            }
+        }
+
+        let mut index = 0;
+
+        while index < self.recordings.len() {
+            let recording = &mut self.recordings[index];
+            recording.events.push((event.clone(), span));
+
+            match event {
+                Event::SequenceStart { .. } | Event::MappingStart { .. } => recording.depth += 1,
+                Event::SequenceEnd | Event::MappingEnd => recording.depth -= 1,
+                _ => {},
+            }
+
+            if recording.depth == 0 {
+                let recording = self.recordings.remove(index);
+                self.anchors.insert(recording.anchor, recording.events);
+            } else {
+                index += 1;
+            }
+        }
+    }
+
+    fn skip_node(&mut self) -> Result<(), DeserializerError> {
+        let mut depth = 0usize;
+
+        loop {
+            match self.next()? {
+                (Event::SequenceStart { .. }, _) | (Event::MappingStart { .. }, _) => {
+                    depth += 1;
+                },
+                (Event::SequenceEnd, _) | (Event::MappingEnd, _) => {
+                    depth -= 1;
+                },
+                (Event::Scalar { .. }, _) => {},
+                (event, span) => {
+                    return Err(unexpected(&event, span));
+                },
+            }
+
+            if depth == 0 {
+                return Ok(());
+            }
+        }
+    }
+
+    fn enter(&mut self, mark: Mark) -> Result<(), DeserializerError> {
+        if self.remaining_depth == 0 {
+            Err(error("recursion limit exceeded", mark))
+        } else {
+            self.remaining_depth -= 1;
+            Ok(())
+        }
+    }
+
+    fn leave(&mut self) {
+        self.remaining_depth += 1;
+    }
+
+    fn visit_sequence<'de, V: de::Visitor<'de>>(&mut self, visitor: V, span: Span) -> Result<V::Value, DeserializerError> {
+        self.enter(span.start)?;
+        let value = visitor.visit_seq(SeqAccess { de: self });
+        self.leave();
+        let value = value.map_err(|e: DeserializerError| e.with_mark(span.start))?;
+
+        match self.next()? {
+            (Event::SequenceEnd, _) => Ok(value),
+            (_, span) => Err(error("trailing sequence items", span.start)),
+        }
+    }
+
+    fn visit_mapping<'de, V: de::Visitor<'de>>(&mut self, visitor: V, span: Span) -> Result<V::Value, DeserializerError> {
+        self.enter(span.start)?;
+        let value = visitor.visit_map(MapAccess { de: self });
+        self.leave();
+        let value = value.map_err(|e: DeserializerError| e.with_mark(span.start))?;
+
+        match self.next()? {
+            (Event::MappingEnd, _) => Ok(value),
+            (_, span) => Err(error("trailing mapping entries", span.start)),
+        }
+    }
+}
+
+impl<'de, 'a, 'b> de::Deserializer<'de> for &'b mut Deserializer<'a> {
+    type Error = DeserializerError;
+
+    fn deserialize_any<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value, DeserializerError> {
+        match self.next()? {
+            (Event::Scalar { tag, value, style, .. }, span) => {
+                visit_scalar(visitor, tag.as_deref(), value, style).map_err(|e: DeserializerError| e.with_mark(span.start))
+            },
+            (Event::SequenceStart { .. }, span) => {
+                self.visit_sequence(visitor, span)
+            },
+            (Event::MappingStart { .. }, span) => {
+                self.visit_mapping(visitor, span)
+            },
+            (event, span) => {
+                Err(unexpected(&event, span))
+            },
+        }
+    }
+
+    fn deserialize_str<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value, DeserializerError> {
+        self.deserialize_string(visitor)
+    }
+
+    fn deserialize_string<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value, DeserializerError> {
+        match self.peek()? {
+            (Event::Scalar { tag: None, .. }, _) => {
+                if let (Event::Scalar { value, .. }, span) = self.next()? {
+                    visitor.visit_string(value).map_err(|e: DeserializerError| e.with_mark(span.start))
+                } else {
+                    unreachable!()
+                }
+            },
+            _ => {
+                self.deserialize_any(visitor)
+            },
+        }
+    }
+
+    fn deserialize_char<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value, DeserializerError> {
+        self.deserialize_string(visitor)
+    }
+
+    fn deserialize_identifier<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value, DeserializerError> {
+        self.deserialize_string(visitor)
+    }
+
+    fn deserialize_bytes<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value, DeserializerError> {
+        self.deserialize_byte_buf(visitor)
+    }
+
+    fn deserialize_byte_buf<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value, DeserializerError> {
+        match self.peek()? {
+            (Event::Scalar { tag: None, .. }, _) => {
+                if let (Event::Scalar { value, .. }, span) = self.next()? {
+                    visitor.visit_byte_buf(value.into_bytes()).map_err(|e: DeserializerError| e.with_mark(span.start))
+                } else {
+                    unreachable!()
+                }
+            },
+            _ => {
+                self.deserialize_any(visitor)
+            },
+        }
+    }
+
+    fn deserialize_option<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value, DeserializerError> {
+        let is_null = match self.peek()? {
+            (Event::Scalar { tag, value, style, .. }, _) => {
+                match tag.as_deref() {
+                    Some(tag::NULL) => true,
+                    Some(_) => false,
+                    None => *style == Some(ScalarStyle::Plain) && Schema::Core.is_null(value),
+                }
+            },
+            _ => false,
+        };
+
+        if is_null {
+            self.next()?;
+            visitor.visit_none()
+        } else {
+            visitor.visit_some(self)
+        }
+    }
+
+    fn deserialize_unit<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value, DeserializerError> {
+        self.deserialize_any(visitor)
+    }
+
+    fn deserialize_unit_struct<V: de::Visitor<'de>>(self, _name: &'static str, visitor: V) -> Result<V::Value, DeserializerError> {
+        self.deserialize_unit(visitor)
+    }
+
+    fn deserialize_newtype_struct<V: de::Visitor<'de>>(self, _name: &'static str, visitor: V) -> Result<V::Value, DeserializerError> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_enum<V: de::Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, DeserializerError> {
+        match self.next()? {
+            (Event::Scalar { value, .. }, span) => {
+                visitor.visit_enum(value.into_deserializer()).map_err(|e: DeserializerError| e.with_mark(span.start))
+            },
+            (Event::MappingStart { .. }, span) => {
+                self.enter(span.start)?;
+                let value = visitor.visit_enum(EnumAccess { de: &mut *self });
+                self.leave();
+                let value = value.map_err(|e: DeserializerError| e.with_mark(span.start))?;
+
+                match self.next()? {
+                    (Event::MappingEnd, _) => Ok(value),
+                    (_, span) => Err(error("expected a mapping with a single key for an enum", span.start)),
+                }
+            },
+            (event, span) => {
+                Err(error(format!("expected a scalar or a mapping for an enum, found {}", event.kind()), span.start))
+            },
+        }
+    }
+
+    fn deserialize_ignored_any<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value, DeserializerError> {
+        self.skip_node()?;
+        visitor.visit_unit()
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64
+        seq tuple tuple_struct map struct
+    }
+}
+
+struct SeqAccess<'b, 'a> {
+    de: &'b mut Deserializer<'a>,
+}
+
+impl<'de> de::SeqAccess<'de> for SeqAccess<'_, '_> {
+    type Error = DeserializerError;
+
+    fn next_element_seed<T: de::DeserializeSeed<'de>>(&mut self, seed: T) -> Result<Option<T::Value>, DeserializerError> {
+        match self.de.peek()? {
+            (Event::SequenceEnd, _) => Ok(None),
+            _ => seed.deserialize(&mut *self.de).map(Some),
+        }
+    }
+}
+
+struct MapAccess<'b, 'a> {
+    de: &'b mut Deserializer<'a>,
+}
+
+impl<'de> de::MapAccess<'de> for MapAccess<'_, '_> {
+    type Error = DeserializerError;
+
+    fn next_key_seed<K: de::DeserializeSeed<'de>>(&mut self, seed: K) -> Result<Option<K::Value>, DeserializerError> {
+        match self.de.peek()? {
+            (Event::MappingEnd, _) => Ok(None),
+            _ => seed.deserialize(&mut *self.de).map(Some),
+        }
+    }
+
+    fn next_value_seed<V: de::DeserializeSeed<'de>>(&mut self, seed: V) -> Result<V::Value, DeserializerError> {
+        seed.deserialize(&mut *self.de)
+    }
+}
+
+struct EnumAccess<'b, 'a> {
+    de: &'b mut Deserializer<'a>,
+}
+
+impl<'de, 'b, 'a> de::EnumAccess<'de> for EnumAccess<'b, 'a> {
+    type Error = DeserializerError;
+    type Variant = Self;
+
+    fn variant_seed<V: de::DeserializeSeed<'de>>(self, seed: V) -> Result<(V::Value, Self), DeserializerError> {
+        let variant = seed.deserialize(&mut *self.de)?;
+        Ok((variant, self))
+    }
+}
+
+impl<'de> de::VariantAccess<'de> for EnumAccess<'_, '_> {
+    type Error = DeserializerError;
+
+    fn unit_variant(self) -> Result<(), DeserializerError> {
+        de::Deserialize::deserialize(&mut *self.de)
+    }
+
+    fn newtype_variant_seed<T: de::DeserializeSeed<'de>>(self, seed: T) -> Result<T::Value, DeserializerError> {
+        seed.deserialize(&mut *self.de)
+    }
+
+    fn tuple_variant<V: de::Visitor<'de>>(self, _len: usize, visitor: V) -> Result<V::Value, DeserializerError> {
+        de::Deserializer::deserialize_seq(&mut *self.de, visitor)
+    }
+
+    fn struct_variant<V: de::Visitor<'de>>(self, _fields: &'static [&'static str], visitor: V) -> Result<V::Value, DeserializerError> {
+        de::Deserializer::deserialize_map(&mut *self.de, visitor)
+    }
+}
+
+struct NullDeserializer;
+
+impl<'de> de::Deserializer<'de> for NullDeserializer {
+    type Error = DeserializerError;
+
+    fn deserialize_any<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value, DeserializerError> {
+        visitor.visit_unit()
+    }
+
+    fn deserialize_option<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value, DeserializerError> {
+        visitor.visit_none()
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf unit unit_struct newtype_struct seq tuple tuple_struct
+        map struct enum identifier ignored_any
+    }
+}
+
+fn error<T: fmt::Display>(message: T, mark: Mark) -> DeserializerError {
+    DeserializerError::Message { message: message.to_string(), mark: Some(mark) }
+}
+
+fn unexpected(event: &Event, span: Span) -> DeserializerError {
+    error(format_args!("unexpected {} event", event.kind()), span.start)
+}
+
+fn visit_scalar<'de, V: de::Visitor<'de>>(
+    visitor: V,
+    tag: Option<&str>,
+    value: String,
+    style: Option<ScalarStyle>,
+) -> Result<V::Value, DeserializerError> {
+    let resolved = match tag {
+        Some(tag) => tag,
+        None if style == Some(ScalarStyle::Plain) => Schema::Core.resolve(&value),
+        None => tag::STR,
+    };
+
+    match resolved {
+        tag::NULL => {
+            visitor.visit_unit()
+        },
+        tag::BOOL => {
+            match Schema::Core.parse_bool(&value) {
+                Some(b) => visitor.visit_bool(b),
+                None => Err(invalid_value(&value, "a boolean")),
+            }
+        },
+        tag::INT => {
+            match Schema::Core.parse_int(&value) {
+                Some(i) if i >= i64::MIN as i128 && i <= i64::MAX as i128 => visitor.visit_i64(i as i64),
+                Some(i) if i >= 0 && i <= u64::MAX as i128 => visitor.visit_u64(i as u64),
+                Some(i) => visitor.visit_i128(i),
+                None => Err(invalid_value(&value, "an integer")),
+            }
+        },
+        tag::FLOAT => {
+            match Schema::Core.parse_float(&value) {
+                Some(f) => visitor.visit_f64(f),
+                None => Err(invalid_value(&value, "a floating-point number")),
+            }
+        },
+        tag::BINARY => {
+            match decode_base64(&value) {
+                Some(bytes) => visitor.visit_byte_buf(bytes),
+                None => Err(invalid_value(&value, "base64-encoded binary data")),
+            }
+        },
+        _ => {
+            visitor.visit_string(value)
+        },
+    }
+}
+
+fn invalid_value(value: &str, expected: &str) -> DeserializerError {
+    de::Error::custom(format!("invalid value `{}`, expected {}", value, expected))
+}
+
+fn decode_base64(value: &str) -> Option<Vec<u8>> {
+    let mut bytes = Vec::with_capacity(value.len() / 4 * 3);
+    let mut accumulator = 0u32;
+    let mut bits = 0;
+    let mut padding = 0;
+
+    for c in value.bytes().filter(|b| !b.is_ascii_whitespace()) {
+        let sextet = match c {
+            b'A'..=b'Z' => c - b'A',
+            b'a'..=b'z' => c - b'a' + 26,
+            b'0'..=b'9' => c - b'0' + 52,
+            b'+' => 62,
+            b'/' => 63,
+            b'=' => {
+                padding += 1;
+                continue;
+            },
+            _ => return None,
+        };
+
+        if padding != 0 {
+            return None;
+        }
+
+        accumulator = (accumulator << 6) | sextet as u32;
+        bits += 6;
+
+        if bits >= 8 {
+            bits -= 8;
+            bytes.push((accumulator >> bits) as u8);
+            accumulator &= (1 << bits) - 1;
+        }
+    }
+
+    if padding > 2 || bits >= 6 {
+        None
+    } else {
+        Some(bytes)
+    }
+}
@@ -0,0 +1,70 @@
+use std::error;
+use std::fmt;
+
+use serde::de;
+
+use crate::{Mark, ParserError};
+
+/// Error returned from [`Deserializer`] methods.
+///
+/// [`Deserializer`]: struct.Deserializer.html
+#[derive(Debug)]
+pub enum DeserializerError {
+    /// Parser error.
+    ParserError(Box<ParserError>),
+
+    /// Error reported by a deserialized type or by the deserializer itself.
+    Message {
+        /// Error description.
+        message: String,
+
+        /// Position of the offending node, if known.
+        mark: Option<Mark>,
+    },
+}
+
+impl DeserializerError {
+    pub(crate) fn with_mark(self, mark: Mark) -> Self {
+        match self {
+            Self::Message { message, mark: None } => Self::Message { message, mark: Some(mark) },
+            error => error,
+        }
+    }
+}
+
+impl From<ParserError> for DeserializerError {
+    fn from(parser_error: ParserError) -> Self {
+        Self::ParserError(Box::new(parser_error))
+    }
+}
+
+impl de::Error for DeserializerError {
+    fn custom<T: fmt::Display>(msg: T) -> Self {
+        Self::Message { message: msg.to_string(), mark: None }
+    }
+}
+
+impl fmt::Display for DeserializerError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::ParserError(parser_error) => {
+                write!(f, "{}", parser_error)
+            },
+            Self::Message { message, mark: Some(mark) } => {
+                write!(f, "{} at {}", message, mark)
+            },
+            Self::Message { message, mark: None } => {
+                write!(f, "{}", message)
+            },
+        }
+    }
+}
+
+impl error::Error for DeserializerError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            Self::ParserError(parser_error) => Some(parser_error.as_ref()),
+            Self::Message { .. } => None,
+        }
+    }
+}
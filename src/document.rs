@@ -0,0 +1,379 @@
+use std::borrow::Cow;
+use std::ffi;
+use std::mem;
+use std::os::raw;
+use std::ptr;
+use std::slice;
+
+use crate::{DocumentError, DocumentNode, EventField, MappingStyle, Mark, NodeId};
+use crate::{ScalarStyle, SequenceStyle, Span, TagDirective, VersionDirective};
+use crate::sys;
+
+/// YAML document represented as a graph of nodes.
+///
+/// Documents are produced by [`Parser::load`] and consumed by
+/// [`Emitter::dump`].  Nodes are referred to by [`NodeId`]; the first node
+/// added to a document is its root node.
+///
+/// ```
+/// # use libyaml::*;
+/// #
+/// # fn doctest() -> Result<(), Box<dyn std::error::Error>> {
+/// let mut document = Document::new(None, vec![], true, true)?;
+/// let root = document.add_mapping(None, None)?;
+/// let key = document.add_scalar(None, "name", None)?;
+/// let value = document.add_scalar(None, "app", None)?;
+/// document.append_mapping_pair(root, key, value)?;
+///
+/// let mut output = Vec::new();
+/// let mut emitter = Emitter::new(&mut output)?;
+/// emitter.dump(document)?;
+/// emitter.close()?;
+/// drop(emitter);
+///
+/// assert_eq!(String::from_utf8(output)?, "name: app\n");
+/// # Ok(())
+/// # }
+/// # doctest().unwrap();
+/// ```
+///
+/// [`Parser::load`]: struct.Parser.html#method.load
+/// [`Emitter::dump`]: struct.Emitter.html#method.dump
+/// [`NodeId`]: struct.NodeId.html
+pub struct Document {
+    inner: sys::yaml_document_t,
+}
+
+impl Document {
+    /// Create an empty document.
+    pub fn new(
+        version: Option<VersionDirective>,
+        tags: Vec<TagDirective>,
+        start_implicit: bool,
+        end_implicit: bool,
+    ) -> Result<Self, DocumentError> {
+        fn into_cstring(s: String, field: EventField) -> Result<ffi::CString, DocumentError> {
+            ffi::CString::new(s).map_err(|e| DocumentError::InteriorNul {
+                field,
+                position: e.nul_position(),
+            })
+        }
+
+        unsafe {
+            let mut inner = mem::MaybeUninit::zeroed().assume_init();
+
+            let mut version = version.map(VersionDirective::into_raw);
+            let version_ptr = version.as_mut().map_or(ptr::null_mut(), |v| v);
+
+            let mut raw_handles = Vec::new();
+            let mut raw_prefixes = Vec::new();
+            let mut raw_tags = Vec::new();
+
+            for tag in tags {
+                raw_handles.push(into_cstring(tag.handle, EventField::TagDirectiveHandle)?);
+                raw_prefixes.push(into_cstring(tag.prefix, EventField::TagDirectivePrefix)?);
+
+                let mut raw_tag: sys::yaml_tag_directive_t = mem::MaybeUninit::zeroed().assume_init();
+                raw_tag.handle = raw_handles.last().unwrap().as_ptr() as *mut _;
+                raw_tag.prefix = raw_prefixes.last().unwrap().as_ptr() as *mut _;
+                raw_tags.push(raw_tag);
+            }
+
+            let ret = sys::yaml_document_initialize(
+                &mut inner,
+                version_ptr,
+                raw_tags.as_mut_ptr(),
+                raw_tags.as_mut_ptr().add(raw_tags.len()),
+                start_implicit,
+                end_implicit,
+            );
+
+            if ret.ok { Ok(Self { inner }) } else { Err(DocumentError::LibYamlError) }
+        }
+    }
+
+    /// Take ownership of a raw `yaml_document_t`.
+    pub fn from_raw(raw: sys::yaml_document_t) -> Self {
+        Self { inner: raw }
+    }
+
+    /// Return the raw `yaml_document_t` for this document.  The caller is
+    /// responsible for freeing memory.
+    pub fn into_raw(self) -> sys::yaml_document_t {
+        let inner = unsafe { ptr::read(&self.inner) };
+        mem::forget(self);
+        inner
+    }
+
+    /// Return the version directive.
+    pub fn version(&self) -> Option<VersionDirective> {
+        if self.inner.version_directive.is_null() {
+            None
+        } else {
+            Some(VersionDirective::from_raw(unsafe { *self.inner.version_directive }))
+        }
+    }
+
+    /// Return the list of tag directives.
+    pub fn tags(&self) -> Vec<TagDirective> {
+        let mut tags = Vec::new();
+        let mut tag_ptr = self.inner.tag_directives.start;
+
+        while tag_ptr != self.inner.tag_directives.end {
+            unsafe {
+                tags.push(TagDirective {
+                    handle: from_raw_cstr((*tag_ptr).handle as *const _).into_owned(),
+                    prefix: from_raw_cstr((*tag_ptr).prefix as *const _).into_owned(),
+                });
+                tag_ptr = tag_ptr.offset(1);
+            }
+        }
+
+        tags
+    }
+
+    /// Return true if no document start marker will be emitted.
+    pub fn start_implicit(&self) -> bool {
+        self.inner.start_implicit
+    }
+
+    /// Return true if no document end marker will be emitted.
+    pub fn end_implicit(&self) -> bool {
+        self.inner.end_implicit
+    }
+
+    /// Return the range of the stream occupied by the document.
+    pub fn span(&self) -> Span {
+        Span {
+            start: Mark::from_raw(self.inner.start_mark),
+            end: Mark::from_raw(self.inner.end_mark),
+        }
+    }
+
+    /// Return the root node, or `None` if the document is empty.
+    pub fn root(&self) -> Option<NodeId> {
+        if self.inner.nodes.top != self.inner.nodes.start {
+            Some(NodeId::from_raw(1))
+        } else {
+            None
+        }
+    }
+
+    /// Return a node by its identifier.
+    pub fn node(&self, id: NodeId) -> Option<DocumentNode<'_>> {
+        let raw = self.raw_node(id)?;
+
+        unsafe {
+            let tag = from_raw_cstr(raw.tag as *const _);
+            let span = Span {
+                start: Mark::from_raw(raw.start_mark),
+                end: Mark::from_raw(raw.end_mark),
+            };
+
+            match raw.type_ {
+                sys::YAML_SCALAR_NODE => {
+                    Some(DocumentNode::Scalar {
+                        tag,
+                        value: String::from_utf8_lossy(raw_slice(
+                            raw.data.scalar.value,
+                            raw.data.scalar.length as _,
+                        )),
+                        style: ScalarStyle::from_raw(raw.data.scalar.style),
+                        span,
+                    })
+                },
+                sys::YAML_SEQUENCE_NODE => {
+                    let items = raw.data.sequence.items;
+
+                    Some(DocumentNode::Sequence {
+                        tag,
+                        items: raw_slice(items.start, items.top.offset_from(items.start) as _)
+                            .iter()
+                            .map(|&item| NodeId::from_raw(item))
+                            .collect(),
+                        style: SequenceStyle::from_raw(raw.data.sequence.style),
+                        span,
+                    })
+                },
+                sys::YAML_MAPPING_NODE => {
+                    let pairs = raw.data.mapping.pairs;
+
+                    Some(DocumentNode::Mapping {
+                        tag,
+                        pairs: raw_slice(pairs.start, pairs.top.offset_from(pairs.start) as _)
+                            .iter()
+                            .map(|pair| (NodeId::from_raw(pair.key), NodeId::from_raw(pair.value)))
+                            .collect(),
+                        style: MappingStyle::from_raw(raw.data.mapping.style),
+                        span,
+                    })
+                },
+                _ => {
+                    None
+                },
+            }
+        }
+    }
+
+    /// Add a scalar node; if `tag` is `None`, the `!!str` tag is used.
+    pub fn add_scalar(
+        &mut self,
+        tag: Option<&str>,
+        value: &str,
+        style: Option<ScalarStyle>,
+    ) -> Result<NodeId, DocumentError> {
+        let tag = tag_into_cstring(tag)?;
+
+        let id = unsafe {
+            sys::yaml_document_add_scalar(
+                &mut self.inner,
+                tag.as_ref().map_or(ptr::null(), |cs| cs.as_ptr()) as *const _,
+                value.as_ptr(),
+                value.len() as _,
+                ScalarStyle::option_into_raw(style),
+            )
+        };
+
+        if id != 0 { Ok(NodeId::from_raw(id)) } else { Err(DocumentError::LibYamlError) }
+    }
+
+    /// Add a sequence node; if `tag` is `None`, the `!!seq` tag is used.
+    pub fn add_sequence(
+        &mut self,
+        tag: Option<&str>,
+        style: Option<SequenceStyle>,
+    ) -> Result<NodeId, DocumentError> {
+        let tag = tag_into_cstring(tag)?;
+
+        let id = unsafe {
+            sys::yaml_document_add_sequence(
+                &mut self.inner,
+                tag.as_ref().map_or(ptr::null(), |cs| cs.as_ptr()) as *const _,
+                SequenceStyle::option_into_raw(style),
+            )
+        };
+
+        if id != 0 { Ok(NodeId::from_raw(id)) } else { Err(DocumentError::LibYamlError) }
+    }
+
+    /// Add a mapping node; if `tag` is `None`, the `!!map` tag is used.
+    pub fn add_mapping(
+        &mut self,
+        tag: Option<&str>,
+        style: Option<MappingStyle>,
+    ) -> Result<NodeId, DocumentError> {
+        let tag = tag_into_cstring(tag)?;
+
+        let id = unsafe {
+            sys::yaml_document_add_mapping(
+                &mut self.inner,
+                tag.as_ref().map_or(ptr::null(), |cs| cs.as_ptr()) as *const _,
+                MappingStyle::option_into_raw(style),
+            )
+        };
+
+        if id != 0 { Ok(NodeId::from_raw(id)) } else { Err(DocumentError::LibYamlError) }
+    }
+
+    /// Append an item to a sequence node.
+    pub fn append_sequence_item(
+        &mut self,
+        sequence: NodeId,
+        item: NodeId,
+    ) -> Result<(), DocumentError> {
+        match self.raw_node(sequence) {
+            Some(raw) if raw.type_ == sys::YAML_SEQUENCE_NODE => {},
+            Some(_) => return Err(DocumentError::NotASequence(sequence)),
+            None => return Err(DocumentError::InvalidNodeId(sequence)),
+        }
+
+        self.check_node_id(item)?;
+
+        if unsafe {
+            sys::yaml_document_append_sequence_item(
+                &mut self.inner,
+                sequence.into_raw(),
+                item.into_raw(),
+            )
+        }.ok {
+            Ok(())
+        } else {
+            Err(DocumentError::LibYamlError)
+        }
+    }
+
+    /// Append a key-value pair to a mapping node.
+    pub fn append_mapping_pair(
+        &mut self,
+        mapping: NodeId,
+        key: NodeId,
+        value: NodeId,
+    ) -> Result<(), DocumentError> {
+        match self.raw_node(mapping) {
+            Some(raw) if raw.type_ == sys::YAML_MAPPING_NODE => {},
+            Some(_) => return Err(DocumentError::NotAMapping(mapping)),
+            None => return Err(DocumentError::InvalidNodeId(mapping)),
+        }
+
+        self.check_node_id(key)?;
+        self.check_node_id(value)?;
+
+        if unsafe {
+            sys::yaml_document_append_mapping_pair(
+                &mut self.inner,
+                mapping.into_raw(),
+                key.into_raw(),
+                value.into_raw(),
+            )
+        }.ok {
+            Ok(())
+        } else {
+            Err(DocumentError::LibYamlError)
+        }
+    }
+
+    /// Return raw pointer to the underlying `yaml_document_t`.
+    pub fn as_raw_ptr(&mut self) -> *mut sys::yaml_document_t {
+        &mut self.inner
+    }
+
+    fn raw_node(&self, id: NodeId) -> Option<&sys::yaml_node_t> {
+        let index = id.into_raw();
+        let count = unsafe { self.inner.nodes.top.offset_from(self.inner.nodes.start) };
+
+        if index > 0 && index as isize <= count {
+            Some(unsafe { &*self.inner.nodes.start.offset(index as isize - 1) })
+        } else {
+            None
+        }
+    }
+
+    fn check_node_id(&self, id: NodeId) -> Result<(), DocumentError> {
+        self.raw_node(id).map(|_| ()).ok_or(DocumentError::InvalidNodeId(id))
+    }
+}
+
+impl Drop for Document {
+    fn drop(&mut self) {
+        unsafe {
+            sys::yaml_document_delete(&mut self.inner);
+        }
+    }
+}
+
+unsafe fn from_raw_cstr<'a>(ptr: *const raw::c_char) -> Cow<'a, str> {
+    ffi::CStr::from_ptr(ptr).to_string_lossy()
+}
+
+unsafe fn raw_slice<'a, T>(ptr: *const T, len: usize) -> &'a [T] {
+    if len == 0 { &[] } else { slice::from_raw_parts(ptr, len) }
+}
+
+fn tag_into_cstring(tag: Option<&str>) -> Result<Option<ffi::CString>, DocumentError> {
+    tag.map(|tag| {
+        ffi::CString::new(tag).map_err(|e| DocumentError::InteriorNul {
+            field: EventField::Tag,
+            position: e.nul_position(),
+        })
+    }).transpose()
+}
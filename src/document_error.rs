@@ -0,0 +1,56 @@
+use std::error;
+use std::fmt;
+
+use crate::{EventField, NodeId};
+
+/// Error returned from [`Document`] methods.
+///
+/// [`Document`]: struct.Document.html
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub enum DocumentError {
+    /// String field contains a NUL byte, which LibYAML cannot represent.
+    InteriorNul {
+        /// Offending field.
+        field: EventField,
+
+        /// Byte offset of the NUL byte within the field.
+        position: usize,
+    },
+
+    /// Node identifier does not refer to a node of the document.
+    InvalidNodeId(NodeId),
+
+    /// Node is not a sequence.
+    NotASequence(NodeId),
+
+    /// Node is not a mapping.
+    NotAMapping(NodeId),
+
+    /// LibYAML error.
+    LibYamlError,
+}
+
+impl fmt::Display for DocumentError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::InteriorNul { field, position } => {
+                write!(f, "{} contains a NUL byte at offset {}", field, position)
+            },
+            Self::InvalidNodeId(id) => {
+                write!(f, "no node with index {} in document", id.into_raw())
+            },
+            Self::NotASequence(id) => {
+                write!(f, "node with index {} is not a sequence", id.into_raw())
+            },
+            Self::NotAMapping(id) => {
+                write!(f, "node with index {} is not a mapping", id.into_raw())
+            },
+            Self::LibYamlError => {
+                write!(f, "YAML document operation failed")
+            },
+        }
+    }
+}
+
+impl error::Error for DocumentError {
+}
@@ -0,0 +1,54 @@
+use std::borrow::Cow;
+
+use crate::{MappingStyle, NodeId, ScalarStyle, SequenceStyle, Span};
+
+/// Node of a [`Document`], borrowed from the document.
+///
+/// [`Document`]: struct.Document.html
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub enum DocumentNode<'a> {
+    /// A scalar node.
+    Scalar {
+        /// Tag name.
+        tag: Cow<'a, str>,
+
+        /// Scalar value.
+        value: Cow<'a, str>,
+
+        /// Scalar style; `None` if LibYAML may choose a style.
+        style: Option<ScalarStyle>,
+
+        /// Range of the stream occupied by the node.
+        span: Span,
+    },
+
+    /// A sequence node.
+    Sequence {
+        /// Tag name.
+        tag: Cow<'a, str>,
+
+        /// Sequence items.
+        items: Vec<NodeId>,
+
+        /// Sequence style; `None` if LibYAML may choose a style.
+        style: Option<SequenceStyle>,
+
+        /// Range of the stream occupied by the node.
+        span: Span,
+    },
+
+    /// A mapping node.
+    Mapping {
+        /// Tag name.
+        tag: Cow<'a, str>,
+
+        /// Mapping key-value pairs.
+        pairs: Vec<(NodeId, NodeId)>,
+
+        /// Mapping style; `None` if LibYAML may choose a style.
+        style: Option<MappingStyle>,
+
+        /// Range of the stream occupied by the node.
+        span: Span,
+    },
+}
@@ -0,0 +1,63 @@
+use crate::{Emitter, EmitterError, Event, Node};
+
+/// Writer of [`Node`] trees as documents of a YAML stream.
+///
+/// ```
+/// # use libyaml::*;
+/// #
+/// # fn doctest() -> Result<(), Box<dyn std::error::Error>> {
+/// let mut output = Vec::new();
+/// let mut dumper = Dumper::new(Emitter::new(&mut output)?);
+///
+/// for node in Loader::new(Parser::new(&b"[a, b]"[..])?.into_spanned_iter()) {
+///     dumper.dump(node?)?;
+/// }
+///
+/// dumper.finish()?;
+/// # Ok(())
+/// # }
+/// # doctest().unwrap();
+/// ```
+///
+/// [`Node`]: enum.Node.html
+pub struct Dumper<'a> {
+    emitter: Box<Emitter<'a>>,
+    started: bool,
+}
+
+impl<'a> Dumper<'a> {
+    /// Create a dumper writing to the given emitter.  No events must have been
+    /// emitted yet.
+    pub fn new(emitter: Box<Emitter<'a>>) -> Self {
+        Self { emitter, started: false }
+    }
+
+    /// Write a node as a document with implicit start and end markers.
+    pub fn dump(&mut self, node: Node) -> Result<(), EmitterError> {
+        self.start()?;
+
+        self.emitter.emit(Event::DocumentStart { version: None, tags: Vec::new(), implicit: true })?;
+
+        for event in node.into_events() {
+            self.emitter.emit(event)?;
+        }
+
+        self.emitter.emit(Event::DocumentEnd { implicit: true })
+    }
+
+    /// Finish the stream and return the emitter.
+    pub fn finish(mut self) -> Result<Box<Emitter<'a>>, EmitterError> {
+        self.start()?;
+        self.emitter.emit(Event::StreamEnd)?;
+        Ok(self.emitter)
+    }
+
+    fn start(&mut self) -> Result<(), EmitterError> {
+        if !self.started {
+            self.emitter.emit(Event::StreamStart { encoding: None })?;
+            self.started = true;
+        }
+
+        Ok(())
+    }
+}
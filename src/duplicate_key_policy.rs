@@ -0,0 +1,35 @@
+use std::fmt;
+
+use crate::Mark;
+
+/// Handling of duplicate mapping keys by [`DuplicateKeyValidator`].
+///
+/// [`DuplicateKeyValidator`]: struct.DuplicateKeyValidator.html
+pub enum DuplicateKeyPolicy<'a> {
+    /// Fail with [`ParserError::DuplicateKey`].
+    ///
+    /// [`ParserError::DuplicateKey`]: enum.ParserError.html#variant.DuplicateKey
+    Error,
+
+    /// Call the function with the positions of the first and the second
+    /// occurrence of the key, and keep both pairs.
+    Warn(Box<dyn FnMut(Mark, Mark) + 'a>),
+
+    /// Keep the first pair with a given key and drop later ones.
+    FirstWins,
+
+    /// Keep the last pair with a given key and drop earlier ones.  Mappings are
+    /// buffered until their end.
+    LastWins,
+}
+
+impl fmt::Debug for DuplicateKeyPolicy<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Error => write!(f, "Error"),
+            Self::Warn(_) => write!(f, "Warn(..)"),
+            Self::FirstWins => write!(f, "FirstWins"),
+            Self::LastWins => write!(f, "LastWins"),
+        }
+    }
+}
@@ -0,0 +1,361 @@
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::collections::VecDeque;
+use std::iter::FusedIterator;
+
+use crate::{DuplicateKeyPolicy, Event, Mark, ParserError, Span};
+use crate::key::Key;
+
+/// Iterator adapter that detects duplicate mapping keys.
+///
+/// The validator consumes events paired with their spans, as produced by
+/// [`ParserSpannedIter`].  Scalar keys are compared after tag resolution
+/// under the core schema, so `1`, `0x1` and `!!int "1"` are the same key;
+/// collection keys are compared structurally.  Aliases are compared by anchor
+/// name, unless they have been expanded with [`AliasExpander`].  What happens
+/// to duplicates depends on the [`DuplicateKeyPolicy`].  When a dropped pair
+/// defines an anchor that a kept alias refers to, the validator fails with
+/// [`ParserError::UnknownAnchor`] rather than produce a dangling alias.  The
+/// iterator is fused, and produces `None` forever after the end of stream or
+/// after a first encountered error.
+///
+/// ```
+/// # use libyaml::*;
+/// #
+/// # fn doctest() -> Result<(), ParserError> {
+/// let parser = Parser::from_str("{a: 1, b: 2, a: 3}")?;
+/// let events = DuplicateKeyValidator::new(parser.into_spanned_iter(), DuplicateKeyPolicy::Error);
+/// let result = events.collect::<Result<Vec<_>, _>>();
+/// assert!(matches!(result, Err(ParserError::DuplicateKey { .. })));
+///
+/// let parser = Parser::from_str("{a: 1, b: 2, a: 3}")?;
+/// let events = DuplicateKeyValidator::new(parser.into_spanned_iter(), DuplicateKeyPolicy::LastWins);
+/// let root = Loader::new(events).next().unwrap()?;
+///
+/// let pairs = match root {
+///     Node::Mapping { pairs, .. } => pairs,
+///     node => panic!("expected a mapping, got {:?}", node),
+/// };
+///
+/// assert_eq!(pairs.len(), 2);
+/// assert!(matches!(&pairs[1], (Node::Scalar { value: key, .. }, Node::Scalar { value, .. }) if key == "a" && value == "3"));
+///
+/// let parser = Parser::from_str("{a: &x {q: 1}, b: *x, a: 7}")?;
+/// let events = DuplicateKeyValidator::new(parser.into_spanned_iter(), DuplicateKeyPolicy::LastWins);
+/// let result = events.collect::<Result<Vec<_>, _>>();
+/// assert!(matches!(result, Err(ParserError::UnknownAnchor { ref anchor, .. }) if anchor == "x"));
+/// # Ok(())
+/// # }
+/// # doctest().unwrap();
+/// ```
+///
+/// [`ParserSpannedIter`]: struct.ParserSpannedIter.html
+/// [`AliasExpander`]: struct.AliasExpander.html
+/// [`DuplicateKeyPolicy`]: enum.DuplicateKeyPolicy.html
+/// [`ParserError::UnknownAnchor`]: enum.ParserError.html#variant.UnknownAnchor
+pub struct DuplicateKeyValidator<'a, I> {
+    events: I,
+    policy: DuplicateKeyPolicy<'a>,
+    frames: Vec<Frame>,
+    ready: VecDeque<(Event, Span)>,
+    anchors: HashSet<String>,
+    fuse_burnt: bool,
+}
+
+enum Frame {
+    Sequence,
+    Mapping(MappingFrame),
+}
+
+#[derive(Default)]
+struct MappingFrame {
+    keys: HashMap<Key, (Mark, usize)>,
+    in_key: bool,
+    key_builder: Vec<PartialKey>,
+    key_mark: Mark,
+    pending: Vec<(Event, Span)>,
+    pairs: Vec<Option<Vec<(Event, Span)>>>,
+    drop_value: bool,
+}
+
+enum PartialKey {
+    Sequence(Option<String>, Vec<Key>),
+    Mapping(Option<String>, Vec<(Key, Key)>, Option<Key>),
+}
+
+impl<'a, I> DuplicateKeyValidator<'a, I>
+where
+    I: Iterator<Item = Result<(Event, Span), ParserError>>,
+{
+    /// Create a validator consuming the given events.
+    pub fn new(events: I, policy: DuplicateKeyPolicy<'a>) -> Self {
+        Self { events, policy, frames: Vec::new(), ready: VecDeque::new(), anchors: HashSet::new(), fuse_burnt: false }
+    }
+
+    fn next_event(&mut self) -> Result<Option<(Event, Span)>, ParserError> {
+        loop {
+            if let Some((event, span)) = self.ready.pop_front() {
+                self.check_anchors(&event, span)?;
+                return Ok(Some((event, span)));
+            }
+
+            match self.events.next().transpose()? {
+                Some((event, span)) => self.process(event, span)?,
+                None => return Ok(None),
+            }
+        }
+    }
+
+    fn process(&mut self, event: Event, span: Span) -> Result<(), ParserError> {
+        let mut completed_key = None;
+
+        for (index, frame) in self.frames.iter_mut().enumerate() {
+            if let Frame::Mapping(mapping) = frame {
+                if mapping.in_key {
+                    if let Some(key) = mapping.feed_key(&event, span) {
+                        completed_key = Some((index, key));
+                    }
+                }
+            }
+        }
+
+        let completes_node = match event {
+            Event::SequenceStart { .. } | Event::MappingStart { .. } => {
+                let frame = match event {
+                    Event::SequenceStart { .. } => Frame::Sequence,
+                    _ => Frame::Mapping(MappingFrame { in_key: true, ..MappingFrame::default() }),
+                };
+
+                self.route(self.frames.len(), vec![(event, span)]);
+                self.frames.push(frame);
+                false
+            },
+            Event::SequenceEnd | Event::MappingEnd => {
+                let mut events = match self.frames.pop() {
+                    Some(Frame::Mapping(mapping)) => mapping.into_kept_pairs(),
+                    _ => Vec::new(),
+                };
+
+                events.push((event, span));
+                self.route(self.frames.len(), events);
+                true
+            },
+            Event::Scalar { .. } | Event::Alias { .. } => {
+                self.route(self.frames.len(), vec![(event, span)]);
+                true
+            },
+            _ => {
+                self.route(self.frames.len(), vec![(event, span)]);
+                false
+            },
+        };
+
+        if !completes_node {
+            return Ok(());
+        }
+
+        match completed_key {
+            Some((index, key)) => {
+                self.complete_key(index, key)
+            },
+            None => {
+                if let Some(Frame::Mapping(mapping)) = self.frames.last_mut() {
+                    mapping.complete_value();
+                }
+
+                Ok(())
+            },
+        }
+    }
+
+    fn complete_key(&mut self, index: usize, key: Key) -> Result<(), ParserError> {
+        let mapping = match &mut self.frames[index] {
+            Frame::Mapping(mapping) => mapping,
+            Frame::Sequence => unreachable!(),
+        };
+
+        mapping.in_key = false;
+        let second = mapping.key_mark;
+        let pair_index = mapping.pairs.len();
+
+        match mapping.keys.get(&key).copied() {
+            None => {
+                mapping.keys.insert(key, (second, pair_index));
+            },
+            Some((first, first_index)) => {
+                match &mut self.policy {
+                    DuplicateKeyPolicy::Error => {
+                        return Err(ParserError::DuplicateKey { first, second });
+                    },
+                    DuplicateKeyPolicy::Warn(warn) => {
+                        warn(first, second);
+                    },
+                    DuplicateKeyPolicy::FirstWins => {
+                        mapping.pending.clear();
+                        mapping.drop_value = true;
+                    },
+                    DuplicateKeyPolicy::LastWins => {
+                        mapping.pairs[first_index] = None;
+                        mapping.keys.insert(key, (second, pair_index));
+                    },
+                }
+            },
+        }
+
+        if let DuplicateKeyPolicy::FirstWins = self.policy {
+            let pending = std::mem::take(&mut mapping.pending);
+            self.route(index, pending);
+        }
+
+        Ok(())
+    }
+
+    fn check_anchors(&mut self, event: &Event, span: Span) -> Result<(), ParserError> {
+        if let DuplicateKeyPolicy::Error | DuplicateKeyPolicy::Warn(_) = self.policy {
+            return Ok(());
+        }
+
+        match event {
+            Event::DocumentStart { .. } => {
+                self.anchors.clear();
+            },
+            Event::Scalar { anchor: Some(anchor), .. }
+            | Event::SequenceStart { anchor: Some(anchor), .. }
+            | Event::MappingStart { anchor: Some(anchor), .. } => {
+                self.anchors.insert(anchor.clone());
+            },
+            Event::Alias { anchor } if !self.anchors.contains(anchor) => {
+                return Err(ParserError::UnknownAnchor { anchor: anchor.clone(), mark: span.start });
+            },
+            _ => {},
+        }
+
+        Ok(())
+    }
+
+    fn route(&mut self, levels: usize, events: Vec<(Event, Span)>) {
+        if events.is_empty() {
+            return;
+        }
+
+        for frame in self.frames[..levels].iter_mut().rev() {
+            if let Frame::Mapping(mapping) = frame {
+                match self.policy {
+                    DuplicateKeyPolicy::FirstWins if mapping.drop_value => {
+                        return;
+                    },
+                    DuplicateKeyPolicy::FirstWins if mapping.in_key => {
+                        mapping.pending.extend(events);
+                        return;
+                    },
+                    DuplicateKeyPolicy::LastWins => {
+                        mapping.pending.extend(events);
+                        return;
+                    },
+                    _ => {},
+                }
+            }
+        }
+
+        self.ready.extend(events);
+    }
+}
+
+impl MappingFrame {
+    fn feed_key(&mut self, event: &Event, span: Span) -> Option<Key> {
+        if self.key_builder.is_empty() {
+            self.key_mark = span.start;
+        }
+
+        let key = match event {
+            Event::Scalar { tag, value, style, .. } => {
+                Key::scalar(tag.as_deref(), value, *style)
+            },
+            Event::Alias { anchor } => {
+                Key::Alias(anchor.clone())
+            },
+            Event::SequenceStart { tag, .. } => {
+                self.key_builder.push(PartialKey::Sequence(tag.clone(), Vec::new()));
+                return None;
+            },
+            Event::MappingStart { tag, .. } => {
+                self.key_builder.push(PartialKey::Mapping(tag.clone(), Vec::new(), None));
+                return None;
+            },
+            Event::SequenceEnd | Event::MappingEnd => {
+                match self.key_builder.pop()? {
+                    PartialKey::Sequence(tag, items) => Key::sequence(tag.as_deref(), items),
+                    PartialKey::Mapping(tag, pairs, _) => Key::mapping(tag.as_deref(), pairs),
+                }
+            },
+            _ => {
+                return None;
+            },
+        };
+
+        match self.key_builder.last_mut() {
+            Some(PartialKey::Sequence(_, items)) => {
+                items.push(key);
+                None
+            },
+            Some(PartialKey::Mapping(_, pairs, pending_key)) => {
+                match pending_key.take() {
+                    Some(pending_key) => pairs.push((pending_key, key)),
+                    None => *pending_key = Some(key),
+                }
+
+                None
+            },
+            None => {
+                Some(key)
+            },
+        }
+    }
+
+    fn complete_value(&mut self) {
+        if self.drop_value {
+            self.drop_value = false;
+        } else {
+            self.pairs.push(Some(std::mem::take(&mut self.pending)));
+        }
+
+        self.in_key = true;
+    }
+
+    fn into_kept_pairs(self) -> Vec<(Event, Span)> {
+        self.pairs.into_iter().flatten().flatten().collect()
+    }
+}
+
+impl<I> Iterator for DuplicateKeyValidator<'_, I>
+where
+    I: Iterator<Item = Result<(Event, Span), ParserError>>,
+{
+    type Item = Result<(Event, Span), ParserError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.fuse_burnt {
+            None
+        } else {
+            match self.next_event() {
+                Ok(Some(event)) => {
+                    Some(Ok(event))
+                },
+                Ok(None) => {
+                    self.fuse_burnt = true;
+                    None
+                },
+                Err(e) => {
+                    self.fuse_burnt = true;
+                    Some(Err(e))
+                },
+            }
+        }
+    }
+}
+
+impl<I> FusedIterator for DuplicateKeyValidator<'_, I>
+where
+    I: Iterator<Item = Result<(Event, Span), ParserError>>,
+{
+}
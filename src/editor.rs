@@ -0,0 +1,790 @@
+use std::ops::Range;
+
+use crate::{EditorError, EmitterBuilder, EmitterError, Event, Loader, MappingStyle, Node, Parser, ParserError};
+use crate::{Path, PathSegment, ScalarStyle, SequenceStyle, Span};
+use crate::tag::{self, Schema};
+
+/// Editor changing a YAML document in place, leaving untouched text as is.
+///
+/// Each operation parses the current text to locate nodes by their spans, and
+/// splices in new nodes emitted by an [`Emitter`] in flow style, so that
+/// quoting follows the emitter's rules.  New nodes take a single line: line
+/// breaks within scalars are escaped in double quotes.  Comments, formatting,
+/// line endings and every byte outside of the edited range are kept.  Paths
+/// are looked up in the first document of the stream.
+///
+/// After each edit, the text is parsed again and compared with the expected
+/// documents.  An edit that cannot be made safely, such as one within a
+/// mapping using explicit `?` keys, fails with [`EditorError::Unsupported`]
+/// and leaves the text unchanged.
+///
+/// ```
+/// # use libyaml::*;
+/// #
+/// # fn doctest() -> Result<(), Box<dyn std::error::Error>> {
+/// let mut editor = Editor::new("\
+/// spec:
+///   image:
+///     name: app  # pinned
+///     tag: '1.0'
+/// ");
+///
+/// let version = Node::Scalar {
+///     anchor: None,
+///     tag: None,
+///     value: "1.1".into(),
+///     plain_implicit: false,
+///     quoted_implicit: true,
+///     style: None,
+///     span: Span::default(),
+/// };
+///
+/// editor.set(&"spec.image.tag".parse()?, version)?;
+/// assert_eq!(editor.as_str(), "\
+/// spec:
+///   image:
+///     name: app  # pinned
+///     tag: '1.1'
+/// ");
+///
+/// let mut editor = Editor::new("? a\n: 1\nb: 2\n");
+/// let result = editor.delete(&"a".parse()?);
+/// assert!(matches!(result, Err(EditorError::Unsupported(_))));
+/// assert_eq!(editor.as_str(), "? a\n: 1\nb: 2\n");
+/// # Ok(())
+/// # }
+/// # doctest().unwrap();
+/// ```
+///
+/// [`Emitter`]: struct.Emitter.html
+/// [`EditorError::Unsupported`]: enum.EditorError.html#variant.Unsupported
+#[derive(Clone, Debug, Default, Eq, Hash, PartialEq)]
+pub struct Editor {
+    source: String,
+}
+
+impl Editor {
+    /// Create an editor for the given text.
+    pub fn new(source: impl Into<String>) -> Self {
+        Self { source: source.into() }
+    }
+
+    /// Return the current text.
+    pub fn as_str(&self) -> &str {
+        &self.source
+    }
+
+    /// Return the current text, consuming the editor.
+    pub fn into_string(self) -> String {
+        self.source
+    }
+
+    /// Replace the scalar at the path with a node.  The anchor of the
+    /// replaced scalar is kept unless the node has its own.  Its tag is kept
+    /// too if the node is an untagged scalar, and dropped otherwise.
+    ///
+    /// ```
+    /// # use libyaml::*;
+    /// #
+    /// # fn doctest() -> Result<(), Box<dyn std::error::Error>> {
+    /// let scalar = |value: &str| Node::Scalar {
+    ///     anchor: None,
+    ///     tag: None,
+    ///     value: value.into(),
+    ///     plain_implicit: true,
+    ///     quoted_implicit: true,
+    ///     style: None,
+    ///     span: Span::default(),
+    /// };
+    ///
+    /// let mut editor = Editor::new("\u{feff}a: !!str 1\nb:\nc: &c x\n");
+    /// editor.set(&"a".parse()?, scalar("2"))?;
+    /// editor.set(&"b".parse()?, scalar("new"))?;
+    /// editor.set(&"c".parse()?, scalar("two\nlines"))?;
+    /// assert_eq!(editor.as_str(), "\u{feff}a: !!str 2\nb: new\nc: &c \"two\\nlines\"\n");
+    /// # Ok(())
+    /// # }
+    /// # doctest().unwrap();
+    /// ```
+    pub fn set(&mut self, path: &Path, value: Node) -> Result<(), EditorError> {
+        let original = self.source.clone();
+        let result = self.try_set(path, value);
+        self.verify(original, path, result)
+    }
+
+    /// Add a pair with a scalar key at the end of the mapping at the path.
+    /// The key is quoted if it would not be read back as a string.
+    ///
+    /// ```
+    /// # use libyaml::*;
+    /// #
+    /// # fn doctest() -> Result<(), Box<dyn std::error::Error>> {
+    /// let scalar = |value: &str| Node::Scalar {
+    ///     anchor: None,
+    ///     tag: None,
+    ///     value: value.into(),
+    ///     plain_implicit: true,
+    ///     quoted_implicit: true,
+    ///     style: None,
+    ///     span: Span::default(),
+    /// };
+    ///
+    /// let mut editor = Editor::new("a:\r\n  x: 1\r\n  y:\r\nb: {p: 1}\r\nc: {}\r\n");
+    /// editor.insert(&"a".parse()?, "z", scalar("3"))?;
+    /// editor.insert(&"b".parse()?, "true", scalar("2"))?;
+    /// editor.insert(&"c".parse()?, "k", scalar("v"))?;
+    /// assert_eq!(editor.as_str(), "a:\r\n  x: 1\r\n  y:\r\n  z: 3\r\nb: {p: 1, 'true': 2}\r\nc: {k: v}\r\n");
+    /// # Ok(())
+    /// # }
+    /// # doctest().unwrap();
+    /// ```
+    pub fn insert(&mut self, path: &Path, key: &str, value: Node) -> Result<(), EditorError> {
+        let original = self.source.clone();
+        let result = self.try_insert(path, key, value);
+        self.verify(original, path, result)
+    }
+
+    /// Add an item at the end of the sequence at the path.
+    ///
+    /// ```
+    /// # use libyaml::*;
+    /// #
+    /// # fn doctest() -> Result<(), Box<dyn std::error::Error>> {
+    /// let scalar = |value: &str| Node::Scalar {
+    ///     anchor: None,
+    ///     tag: None,
+    ///     value: value.into(),
+    ///     plain_implicit: true,
+    ///     quoted_implicit: true,
+    ///     style: None,
+    ///     span: Span::default(),
+    /// };
+    ///
+    /// let mut editor = Editor::new("block:\n  - a\ncompact:\n- a\nflow: [a]\nempty: []\n");
+    /// editor.append(&"block".parse()?, scalar("b"))?;
+    /// editor.append(&"compact".parse()?, scalar("b"))?;
+    /// editor.append(&"flow".parse()?, scalar("b"))?;
+    /// editor.append(&"empty".parse()?, scalar("b"))?;
+    /// assert_eq!(editor.as_str(), "block:\n  - a\n  - b\ncompact:\n- a\n- b\nflow: [a, b]\nempty: [b]\n");
+    /// # Ok(())
+    /// # }
+    /// # doctest().unwrap();
+    /// ```
+    pub fn append(&mut self, path: &Path, value: Node) -> Result<(), EditorError> {
+        let original = self.source.clone();
+        let result = self.try_append(path, value);
+        self.verify(original, path, result)
+    }
+
+    /// Remove the mapping pair or the sequence item at the path.  Comments on
+    /// the lines of the removed entry are removed with it.
+    ///
+    /// ```
+    /// # use libyaml::*;
+    /// #
+    /// # fn doctest() -> Result<(), Box<dyn std::error::Error>> {
+    /// let mut editor = Editor::new("a: 1\nb:  # none\nlist:\n- x\nflow: [x, y]\n");
+    /// editor.delete(&"b".parse()?)?;
+    /// editor.delete(&"list[0]".parse()?)?;
+    /// editor.delete(&"flow[1]".parse()?)?;
+    /// assert_eq!(editor.as_str(), "a: 1\nlist:\n  []\nflow: [x]\n");
+    /// # Ok(())
+    /// # }
+    /// # doctest().unwrap();
+    /// ```
+    pub fn delete(&mut self, path: &Path) -> Result<(), EditorError> {
+        let original = self.source.clone();
+        let result = self.try_delete(path);
+        self.verify(original, path, result)
+    }
+
+    fn try_set(&mut self, path: &Path, mut value: Node) -> Result<Vec<Node>, EditorError> {
+        let mut documents = self.load()?;
+        let root = documents.first().ok_or_else(|| EditorError::NotFound(path.clone()))?;
+        let (target, parent) = find(root, path.segments()).ok_or_else(|| EditorError::NotFound(path.clone()))?;
+
+        let (anchor, tag, span) = match target {
+            Node::Scalar { anchor, tag, span, .. } => (anchor.clone(), tag.clone(), *span),
+            _ => return Err(EditorError::NotAScalar(path.clone())),
+        };
+
+        match &mut value {
+            Node::Scalar { anchor: value_anchor, tag: value_tag, plain_implicit, quoted_implicit, .. } => {
+                if value_anchor.is_none() {
+                    *value_anchor = anchor;
+                }
+
+                if value_tag.is_none() && tag.is_some() {
+                    *value_tag = tag;
+                    *plain_implicit = false;
+                    *quoted_implicit = false;
+                }
+            },
+            Node::Sequence { anchor: value_anchor @ None, .. } | Node::Mapping { anchor: value_anchor @ None, .. } => {
+                *value_anchor = anchor;
+            },
+            _ => {},
+        }
+
+        let text = emit_flow(value.clone())?;
+
+        if span.start.index < span.end.index {
+            let before_end = self.source.get(..span.end.index).unwrap_or("");
+            let line_break = if before_end.ends_with("\r\n") {
+                "\r\n"
+            } else if before_end.ends_with('\n') {
+                "\n"
+            } else {
+                ""
+            };
+
+            self.splice(span.start.index..span.end.index, &format!("{}{}", text, line_break));
+        } else {
+            let indicator = match parent {
+                Some(Node::Mapping { style: Some(MappingStyle::Block), pairs, .. }) => {
+                    if self.has_explicit_keys(pairs) {
+                        return Err(EditorError::Unsupported(path.clone()));
+                    }
+
+                    pairs.iter()
+                        .find(|(_, v)| v.span() == span)
+                        .and_then(|(key, _)| self.colon_after(key))
+                },
+                Some(Node::Sequence { style: Some(SequenceStyle::Block), span: parent_span, items, .. }) => {
+                    let index = items.iter().position(|item| item.span() == span).unwrap_or(0);
+                    let from = if index == 0 { parent_span.start.index } else { self.content_end(&items[index - 1]) };
+                    self.dash_before(from, span.start.index)
+                },
+                _ => {
+                    None
+                },
+            };
+
+            match indicator {
+                Some(position) => self.splice(position + 1..position + 1, &format!(" {}", text)),
+                None => self.splice(span.start.index..span.start.index, &text),
+            }
+        }
+
+        if let Some(target) = find_mut(&mut documents[0], path.segments()) {
+            *target = value;
+        }
+
+        Ok(documents)
+    }
+
+    fn try_insert(&mut self, path: &Path, key: &str, value: Node) -> Result<Vec<Node>, EditorError> {
+        let mut documents = self.load()?;
+        let root = documents.first().ok_or_else(|| EditorError::NotFound(path.clone()))?;
+        let (target, _) = find(root, path.segments()).ok_or_else(|| EditorError::NotFound(path.clone()))?;
+
+        let (style, pairs, span) = match target {
+            Node::Mapping { style, pairs, span, .. } => (*style, pairs, *span),
+            _ => return Err(EditorError::NotAMapping(path.clone())),
+        };
+
+        if pairs.iter().any(|(k, _)| matches!(k, Node::Scalar { value, .. } if value == key)) {
+            let mut path = path.clone();
+            path.push(PathSegment::Key(key.to_string()));
+            return Err(EditorError::KeyExists(path));
+        }
+
+        if self.has_explicit_keys(pairs) {
+            return Err(EditorError::Unsupported(path.clone()));
+        }
+
+        let key = Node::Scalar {
+            anchor: None,
+            tag: None,
+            value: key.to_string(),
+            plain_implicit: Schema::Core.resolve(key) == tag::STR,
+            quoted_implicit: true,
+            style: None,
+            span: Span::default(),
+        };
+
+        let entry = format!("{}: {}", emit_flow(key.clone())?, emit_flow(value.clone())?);
+        let ends = pairs.iter().map(|(key, value)| self.pair_end(key, value)).collect::<Vec<_>>();
+
+        match style {
+            Some(MappingStyle::Block) => {
+                let indent = self.column(pairs[0].0.span().start.index);
+                self.insert_line(ends[ends.len() - 1], indent, &entry);
+            },
+            _ => {
+                self.insert_flow(span, &ends, &entry);
+            },
+        }
+
+        if let Some(Node::Mapping { pairs, .. }) = find_mut(&mut documents[0], path.segments()) {
+            pairs.push((key, value));
+        }
+
+        Ok(documents)
+    }
+
+    fn try_append(&mut self, path: &Path, value: Node) -> Result<Vec<Node>, EditorError> {
+        let mut documents = self.load()?;
+        let root = documents.first().ok_or_else(|| EditorError::NotFound(path.clone()))?;
+        let (target, _) = find(root, path.segments()).ok_or_else(|| EditorError::NotFound(path.clone()))?;
+
+        let (style, span) = match target {
+            Node::Sequence { style, span, .. } => (*style, *span),
+            _ => return Err(EditorError::NotASequence(path.clone())),
+        };
+
+        let text = emit_flow(value.clone())?;
+        let entries = self.item_ranges(target);
+
+        match style {
+            Some(SequenceStyle::Block) => {
+                let indent = self.column(entries[0].start);
+                let end = entries[entries.len() - 1].end;
+                self.insert_line(end, indent, &format!("- {}", text));
+            },
+            _ => {
+                let ends = entries.iter().map(|entry| entry.end).collect::<Vec<_>>();
+                self.insert_flow(span, &ends, &text);
+            },
+        }
+
+        if let Some(Node::Sequence { items, .. }) = find_mut(&mut documents[0], path.segments()) {
+            items.push(value);
+        }
+
+        Ok(documents)
+    }
+
+    fn try_delete(&mut self, path: &Path) -> Result<Vec<Node>, EditorError> {
+        let mut documents = self.load()?;
+        let root = documents.first().ok_or_else(|| EditorError::NotFound(path.clone()))?;
+        let (segment, parent_segments) = path.segments().split_last().ok_or_else(|| EditorError::NotFound(path.clone()))?;
+        let (parent, grandparent) = find(root, parent_segments).ok_or_else(|| EditorError::NotFound(path.clone()))?;
+        let parent_path = Path::from(parent_segments.to_vec());
+
+        let (block, empty, entries, index) = match (segment, parent) {
+            (PathSegment::Key(key), Node::Mapping { style, pairs, .. }) => {
+                let index = pairs.iter()
+                    .position(|(k, _)| matches!(k, Node::Scalar { value, .. } if value == key))
+                    .ok_or_else(|| EditorError::NotFound(path.clone()))?;
+
+                if self.has_explicit_keys(pairs) {
+                    return Err(EditorError::Unsupported(path.clone()));
+                }
+
+                let entries = pairs.iter()
+                    .map(|(key, value)| key.span().start.index..self.pair_end(key, value))
+                    .collect::<Vec<_>>();
+                (*style == Some(MappingStyle::Block), "{}", entries, index)
+            },
+            (PathSegment::Index(index), Node::Sequence { style, items, .. }) if *index < items.len() => {
+                (*style == Some(SequenceStyle::Block), "[]", self.item_ranges(parent), *index)
+            },
+            (PathSegment::Key(_), _) => {
+                return Err(EditorError::NotAMapping(parent_path));
+            },
+            (PathSegment::Index(_), Node::Sequence { .. }) => {
+                return Err(EditorError::NotFound(path.clone()));
+            },
+            (PathSegment::Index(_), _) => {
+                return Err(EditorError::NotASequence(parent_path));
+            },
+            (PathSegment::ComplexKey, _) => {
+                return Err(EditorError::NotFound(path.clone()));
+            },
+        };
+
+        let entry = entries[index].clone();
+
+        let range = if entries.len() == 1 {
+            if block {
+                // A sequence indented no further than its key must be
+                // indented once it becomes a flow collection.
+                let column = self.column(entry.start);
+                let compact = match grandparent {
+                    Some(Node::Mapping { style: Some(MappingStyle::Block), pairs, .. }) => {
+                        pairs.first().is_some_and(|(key, _)| key.span().start.column == column)
+                    },
+                    _ => {
+                        false
+                    },
+                };
+
+                let text = if compact { format!("  {}", empty) } else { empty.to_owned() };
+                self.splice(entry, &text);
+                None
+            } else {
+                Some(entry)
+            }
+        } else if block {
+            let start = line_start(&self.source, entry.start);
+
+            if self.source.get(start..entry.start).is_some_and(|s| s.trim().is_empty()) {
+                Some(start..self.next_line_start(entry.end))
+            } else if index + 1 < entries.len() {
+                Some(entry.start..entries[index + 1].start)
+            } else {
+                Some(entry)
+            }
+        } else if index + 1 < entries.len() {
+            Some(entry.start..entries[index + 1].start)
+        } else {
+            Some(entries[index - 1].end..entry.end)
+        };
+
+        if let Some(range) = range {
+            self.splice(range, "");
+        }
+
+        match find_mut(&mut documents[0], parent_segments) {
+            Some(Node::Mapping { pairs, .. }) => {
+                pairs.remove(index);
+            },
+            Some(Node::Sequence { items, .. }) => {
+                items.remove(index);
+            },
+            _ => {},
+        }
+
+        Ok(documents)
+    }
+
+    fn verify(
+        &mut self,
+        original: String,
+        path: &Path,
+        result: Result<Vec<Node>, EditorError>,
+    ) -> Result<(), EditorError> {
+        let error = match result {
+            Ok(expected) => {
+                match self.load() {
+                    Ok(documents) if documents.len() == expected.len()
+                        && documents.iter().zip(&expected).all(|(actual, expected)| same(actual, expected)) =>
+                    {
+                        return Ok(());
+                    },
+                    _ => {
+                        EditorError::Unsupported(path.clone())
+                    },
+                }
+            },
+            Err(e) => {
+                e
+            },
+        };
+
+        self.source = original;
+        Err(error)
+    }
+
+    fn load(&self) -> Result<Vec<Node>, ParserError> {
+        let mut documents = Loader::new(Parser::from_str(&self.source)?.into_spanned_iter())
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let bom = bom_len(&self.source);
+
+        if bom != 0 {
+            documents.iter_mut().for_each(|document| shift(document, bom));
+        }
+
+        Ok(documents)
+    }
+
+    fn splice(&mut self, range: Range<usize>, text: &str) {
+        // A misplaced range is caught when the result is verified.
+        if range.start <= range.end
+            && self.source.is_char_boundary(range.start)
+            && self.source.is_char_boundary(range.end)
+        {
+            self.source.replace_range(range, text);
+        }
+    }
+
+    fn insert_line(&mut self, end: usize, indent: usize, text: &str) {
+        let line_break = line_break(&self.source, end);
+
+        if line_start(&self.source, end) == end {
+            self.splice(end..end, &format!("{:indent$}{}{}", "", text, line_break, indent = indent));
+        } else {
+            let end = line_end(&self.source, end);
+            self.splice(end..end, &format!("{}{:indent$}{}", line_break, "", text, indent = indent));
+        }
+    }
+
+    fn insert_flow(&mut self, span: Span, ends: &[usize], text: &str) {
+        match ends.last() {
+            Some(&end) => self.splice(end..end, &format!(", {}", text)),
+            None => self.splice(span.end.index.saturating_sub(1)..span.end.index.saturating_sub(1), text),
+        }
+    }
+
+    fn column(&self, position: usize) -> usize {
+        self.source
+            .get(line_start(&self.source, position)..position)
+            .map_or(0, |s| s.chars().count())
+    }
+
+    fn next_line_start(&self, position: usize) -> usize {
+        if line_start(&self.source, position) == position {
+            position
+        } else {
+            self.source
+                .get(position..)
+                .and_then(|s| s.find('\n'))
+                .map_or(self.source.len(), |i| position + i + 1)
+        }
+    }
+
+    fn content_end(&self, node: &Node) -> usize {
+        match node {
+            Node::Sequence { style: Some(SequenceStyle::Block), span, .. } => {
+                self.item_ranges(node).last().map_or(span.end.index, |item| item.end)
+            },
+            Node::Mapping { style: Some(MappingStyle::Block), pairs, span, .. } => {
+                pairs.last().map_or(span.end.index, |(key, value)| self.pair_end(key, value))
+            },
+            node => {
+                node.span().end.index
+            },
+        }
+    }
+
+    fn pair_end(&self, key: &Node, value: &Node) -> usize {
+        let span = value.span();
+
+        if span.start.index < span.end.index {
+            self.content_end(value)
+        } else {
+            self.colon_after(key).map_or(self.content_end(key), |colon| colon + 1)
+        }
+    }
+
+    fn item_ranges(&self, sequence: &Node) -> Vec<Range<usize>> {
+        let (block, items, span) = match sequence {
+            Node::Sequence { style, items, span, .. } => (*style == Some(SequenceStyle::Block), items, *span),
+            _ => return Vec::new(),
+        };
+
+        let mut from = span.start.index;
+
+        items.iter()
+            .map(|item| {
+                let item_span = item.span();
+                let dash = if block { self.dash_before(from, item_span.start.index) } else { None };
+                let start = dash.unwrap_or(item_span.start.index);
+
+                let end = match dash {
+                    Some(dash) if item_span.start.index == item_span.end.index => dash + 1,
+                    _ => self.content_end(item),
+                };
+
+                from = end;
+                start..end
+            })
+            .collect()
+    }
+
+    fn has_explicit_keys(&self, pairs: &[(Node, Node)]) -> bool {
+        pairs.iter().any(|(key, _)| {
+            match key {
+                Node::Scalar { span, .. } => {
+                    let before = self.source.get(..span.start.index).unwrap_or("").trim_end();
+                    before.ends_with('?')
+                        && before[..before.len() - 1].chars().next_back().is_none_or(|c| " \t\r\n{[,-".contains(c))
+                },
+                _ => {
+                    true
+                },
+            }
+        })
+    }
+
+    fn colon_after(&self, key: &Node) -> Option<usize> {
+        let end = self.content_end(key);
+        self.source.get(end..)?.find(':').map(|i| end + i)
+    }
+
+    fn dash_before(&self, from: usize, position: usize) -> Option<usize> {
+        self.source.get(from..position)?.rfind('-').map(|i| from + i)
+    }
+}
+
+fn find<'n>(root: &'n Node, segments: &[PathSegment]) -> Option<(&'n Node, Option<&'n Node>)> {
+    let mut node = root;
+    let mut parent = None;
+
+    for segment in segments {
+        let child = match (segment, node) {
+            (PathSegment::Key(key), Node::Mapping { pairs, .. }) => {
+                pairs.iter()
+                    .find(|(k, _)| matches!(k, Node::Scalar { value, .. } if value == key))
+                    .map(|(_, value)| value)?
+            },
+            (PathSegment::Index(index), Node::Sequence { items, .. }) => {
+                items.get(*index)?
+            },
+            _ => {
+                return None;
+            },
+        };
+
+        parent = Some(node);
+        node = child;
+    }
+
+    Some((node, parent))
+}
+
+fn find_mut<'n>(root: &'n mut Node, segments: &[PathSegment]) -> Option<&'n mut Node> {
+    let mut node = root;
+
+    for segment in segments {
+        node = match (segment, node) {
+            (PathSegment::Key(key), Node::Mapping { pairs, .. }) => {
+                pairs.iter_mut()
+                    .find(|(k, _)| matches!(k, Node::Scalar { value, .. } if value == key))
+                    .map(|(_, value)| value)?
+            },
+            (PathSegment::Index(index), Node::Sequence { items, .. }) => {
+                items.get_mut(*index)?
+            },
+            _ => {
+                return None;
+            },
+        };
+    }
+
+    Some(node)
+}
+
+fn emit_flow(node: Node) -> Result<String, EmitterError> {
+    let mut output = Vec::new();
+
+    {
+        let mut emitter = EmitterBuilder::new(&mut output)?.unicode(true).line_width(usize::MAX).finish();
+
+        emitter.emit(Event::StreamStart { encoding: None })?;
+        emitter.emit(Event::DocumentStart { version: None, tags: Vec::new(), implicit: true })?;
+        emitter.emit(Event::SequenceStart { anchor: None, tag: None, implicit: true, style: Some(SequenceStyle::Flow) })?;
+
+        for event in node.into_events() {
+            emitter.emit(match event {
+                Event::SequenceStart { anchor, tag, implicit, .. } => {
+                    Event::SequenceStart { anchor, tag, implicit, style: Some(SequenceStyle::Flow) }
+                },
+                Event::MappingStart { anchor, tag, implicit, .. } => {
+                    Event::MappingStart { anchor, tag, implicit, style: Some(MappingStyle::Flow) }
+                },
+                Event::Scalar { anchor, tag, value, plain_implicit, quoted_implicit, .. }
+                    if value.contains(['\n', '\r', '\u{85}', '\u{2028}', '\u{2029}']) =>
+                {
+                    let style = Some(ScalarStyle::DoubleQuoted);
+                    Event::Scalar { anchor, tag, value, plain_implicit, quoted_implicit, style }
+                },
+                event => {
+                    event
+                },
+            })?;
+        }
+
+        emitter.emit(Event::SequenceEnd)?;
+        emitter.emit(Event::DocumentEnd { implicit: true })?;
+        emitter.emit(Event::StreamEnd)?;
+    }
+
+    let output = String::from_utf8_lossy(&output);
+    let output = output.trim_end();
+    Ok(output[1..output.len() - 1].to_string())
+}
+
+fn line_start(source: &str, position: usize) -> usize {
+    source.get(..position).and_then(|s| s.rfind('\n')).map_or(bom_len(source), |i| i + 1)
+}
+
+fn line_end(source: &str, position: usize) -> usize {
+    match source.get(position..).and_then(|s| s.find('\n')) {
+        Some(i) if source[..position + i].ends_with('\r') => position + i - 1,
+        Some(i) => position + i,
+        None => source.len(),
+    }
+}
+
+fn line_break(source: &str, position: usize) -> &'static str {
+    let next = source.get(position..).and_then(|s| s.find('\n')).map(|i| position + i);
+    let previous = source.get(..position).and_then(|s| s.rfind('\n'));
+
+    match next.or(previous) {
+        Some(i) if source[..i].ends_with('\r') => "\r\n",
+        _ => "\n",
+    }
+}
+
+fn bom_len(source: &str) -> usize {
+    if source.starts_with('\u{feff}') { '\u{feff}'.len_utf8() } else { 0 }
+}
+
+fn shift(node: &mut Node, offset: usize) {
+    match node {
+        Node::Scalar { span, .. } | Node::Alias { span, .. } => {
+            span.start.index += offset;
+            span.end.index += offset;
+        },
+        Node::Sequence { items, span, .. } => {
+            span.start.index += offset;
+            span.end.index += offset;
+            items.iter_mut().for_each(|item| shift(item, offset));
+        },
+        Node::Mapping { pairs, span, .. } => {
+            span.start.index += offset;
+            span.end.index += offset;
+
+            for (key, value) in pairs {
+                shift(key, offset);
+                shift(value, offset);
+            }
+        },
+    }
+}
+
+fn same(actual: &Node, expected: &Node) -> bool {
+    // An untagged expected node may come back with a resolved tag, such as
+    // the tag kept by `set`; styles and spans are up to the emitter.
+    fn same_tag(actual: &Option<String>, expected: &Option<String>) -> bool {
+        actual.is_none() || expected.is_none() || actual == expected
+    }
+
+    match (actual, expected) {
+        (
+            Node::Scalar { anchor, tag, value, .. },
+            Node::Scalar { anchor: expected_anchor, tag: expected_tag, value: expected_value, .. },
+        ) => {
+            anchor == expected_anchor && same_tag(tag, expected_tag) && value == expected_value
+        },
+        (
+            Node::Sequence { anchor, tag, items, .. },
+            Node::Sequence { anchor: expected_anchor, tag: expected_tag, items: expected_items, .. },
+        ) => {
+            anchor == expected_anchor
+                && same_tag(tag, expected_tag)
+                && items.len() == expected_items.len()
+                && items.iter().zip(expected_items).all(|(item, expected)| same(item, expected))
+        },
+        (
+            Node::Mapping { anchor, tag, pairs, .. },
+            Node::Mapping { anchor: expected_anchor, tag: expected_tag, pairs: expected_pairs, .. },
+        ) => {
+            anchor == expected_anchor
+                && same_tag(tag, expected_tag)
+                && pairs.len() == expected_pairs.len()
+                && pairs.iter().zip(expected_pairs).all(|((key, value), (expected_key, expected_value))| {
+                    same(key, expected_key) && same(value, expected_value)
+                })
+        },
+        (Node::Alias { anchor, .. }, Node::Alias { anchor: expected_anchor, .. }) => {
+            anchor == expected_anchor
+        },
+        _ => {
+            false
+        },
+    }
+}
@@ -0,0 +1,94 @@
+use std::error;
+use std::fmt;
+
+use crate::{EmitterError, ParserError, Path};
+
+/// Error returned from [`Editor`] methods.
+///
+/// [`Editor`]: struct.Editor.html
+#[derive(Debug)]
+pub enum EditorError {
+    /// Edited text could not be parsed.
+    ParserError(Box<ParserError>),
+
+    /// New node could not be emitted.
+    EmitterError(EmitterError),
+
+    /// No node at the path.
+    NotFound(Path),
+
+    /// Node at the path is not a scalar.
+    NotAScalar(Path),
+
+    /// Node at the path is not a sequence.
+    NotASequence(Path),
+
+    /// Node at the path is not a mapping.
+    NotAMapping(Path),
+
+    /// Mapping already contains the key.
+    KeyExists(Path),
+
+    /// Edit cannot be made in place, for example because the mapping uses
+    /// explicit `?` keys, or because the edited text would not read back as
+    /// the expected document.  The text is left unchanged.
+    Unsupported(Path),
+}
+
+impl From<ParserError> for EditorError {
+    fn from(parser_error: ParserError) -> Self {
+        Self::ParserError(Box::new(parser_error))
+    }
+}
+
+impl From<EmitterError> for EditorError {
+    fn from(emitter_error: EmitterError) -> Self {
+        Self::EmitterError(emitter_error)
+    }
+}
+
+impl fmt::Display for EditorError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::ParserError(parser_error) => {
+                write!(f, "cannot parse document: {}", parser_error)
+            },
+            Self::EmitterError(emitter_error) => {
+                write!(f, "cannot emit node: {}", emitter_error)
+            },
+            Self::NotFound(path) => {
+                write!(f, "no node at `{}`", path)
+            },
+            Self::NotAScalar(path) => {
+                write!(f, "node at `{}` is not a scalar", path)
+            },
+            Self::NotASequence(path) => {
+                write!(f, "node at `{}` is not a sequence", path)
+            },
+            Self::NotAMapping(path) => {
+                write!(f, "node at `{}` is not a mapping", path)
+            },
+            Self::KeyExists(path) => {
+                write!(f, "key `{}` already exists", path)
+            },
+            Self::Unsupported(path) => {
+                write!(f, "cannot edit `{}` in place", path)
+            },
+        }
+    }
+}
+
+impl error::Error for EditorError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            Self::ParserError(parser_error) => Some(parser_error.as_ref()),
+            Self::EmitterError(emitter_error) => Some(emitter_error),
+            Self::NotFound(_) => None,
+            Self::NotAScalar(_) => None,
+            Self::NotASequence(_) => None,
+            Self::NotAMapping(_) => None,
+            Self::KeyExists(_) => None,
+            Self::Unsupported(_) => None,
+        }
+    }
+}
@@ -3,7 +3,8 @@ use std::mem;
 use std::os::raw;
 use std::slice;
 
-use crate::{EmitterError, Event};
+use crate::{Document, EmitterError, Event, EventValidator, OwnedEmitter};
+use crate::event_validator::node_anchor;
 use crate::sys;
 
 /// Emitter.
@@ -11,6 +12,9 @@ pub struct Emitter<'a> {
     inner: sys::yaml_emitter_t,
     writer: Box<dyn io::Write + 'a>,
     writer_error: Option<io::Error>,
+    opened: bool,
+    failed: bool,
+    pub(crate) validator: Option<EventValidator>,
 }
 
 impl<'a> Emitter<'a> {
@@ -28,6 +32,9 @@ impl<'a> Emitter<'a> {
                 inner,
                 writer: Box::new(writer),
                 writer_error: None,
+                opened: false,
+                failed: false,
+                validator: None,
             });
 
             unsafe {
@@ -40,19 +47,223 @@ impl<'a> Emitter<'a> {
 
             Ok(emitter)
         } else {
-            Err(EmitterError::LibYamlError)
+            Err(EmitterError::from_raw(&inner, None))
         }
     }
 
+    /// Emit a stream of events with default configuration into a byte vector.
+    ///
+    /// The events must form a complete stream, from *STREAM-START* to
+    /// *STREAM-END*.
+    pub fn to_vec<I: IntoIterator<Item = Event>>(events: I) -> Result<Vec<u8>, EmitterError> {
+        let mut emitter = OwnedEmitter::new(Vec::new())?;
+
+        for event in events {
+            emitter.emit(event)?;
+        }
+
+        emitter.into_inner()
+    }
+
+    /// Emit a stream of events with default configuration into a string.  See
+    /// [`to_vec`].  An error is returned if the stream requests an encoding
+    /// other than UTF-8.
+    ///
+    /// ```
+    /// # use libyaml::*;
+    /// #
+    /// # fn doctest() -> Result<(), EmitterError> {
+    /// let yaml = "a: [1, 2]\n";
+    /// let events = Parser::from_str(yaml).unwrap().into_iter().collect::<Result<Vec<_>, _>>().unwrap();
+    /// assert_eq!(Emitter::to_string(events)?, yaml);
+    /// # Ok(())
+    /// # }
+    /// # doctest().unwrap();
+    /// ```
+    ///
+    /// [`to_vec`]: #method.to_vec
+    pub fn to_string<I: IntoIterator<Item = Event>>(events: I) -> Result<String, EmitterError> {
+        String::from_utf8(Self::to_vec(events)?)
+            .map_err(|e| EmitterError::IoError(io::Error::new(io::ErrorKind::InvalidData, e)))
+    }
+
     /// Emit an event.
+    ///
+    /// If validation is enabled with [`EmitterBuilder::validate`], an event
+    /// that would make the stream ill-formed is rejected before it reaches
+    /// LibYAML.  Such an event, or one that cannot be converted for LibYAML,
+    /// leaves the emitter unchanged and may be corrected and emitted again.
+    ///
+    /// LibYAML queues an event before writing it out, so after an I/O or
+    /// LibYAML error the state of the stream is unknown; every later call then
+    /// fails with [`EmitterError::Failed`].
+    ///
+    /// ```
+    /// # use std::io;
+    /// # use libyaml::*;
+    /// #
+    /// struct Broken;
+    ///
+    /// impl io::Write for Broken {
+    ///     fn write(&mut self, _: &[u8]) -> io::Result<usize> {
+    ///         Err(io::ErrorKind::BrokenPipe.into())
+    ///     }
+    ///
+    ///     fn flush(&mut self) -> io::Result<()> {
+    ///         Ok(())
+    ///     }
+    /// }
+    ///
+    /// # fn doctest() -> Result<(), EmitterError> {
+    /// let mut emitter = Emitter::new(Broken)?;
+    /// emitter.emit(Event::StreamStart { encoding: None })?;
+    /// emitter.emit(Event::DocumentStart { version: None, tags: vec![], implicit: true })?;
+    /// emitter.emit(Event::Scalar {
+    ///     anchor: None,
+    ///     tag: None,
+    ///     value: "a".to_string(),
+    ///     plain_implicit: true,
+    ///     quoted_implicit: true,
+    ///     style: None,
+    /// })?;
+    ///
+    /// let result = emitter.emit(Event::DocumentEnd { implicit: true });
+    /// assert!(matches!(result, Err(EmitterError::IoError(_))));
+    ///
+    /// let result = emitter.emit(Event::DocumentEnd { implicit: true });
+    /// assert!(matches!(result, Err(EmitterError::Failed)));
+    /// # Ok(())
+    /// # }
+    /// # doctest().unwrap();
+    /// ```
+    ///
+    /// [`EmitterBuilder::validate`]: struct.EmitterBuilder.html#method.validate
+    /// [`EmitterError::Failed`]: enum.EmitterError.html#variant.Failed
     pub fn emit(&mut self, event: Event) -> Result<(), EmitterError> {
+        if self.failed {
+            return Err(EmitterError::Failed);
+        }
+
+        let kind = event.kind();
+
+        let anchor = match &self.validator {
+            Some(validator) => {
+                validator.check(&event)?;
+                node_anchor(&event).cloned()
+            },
+            None => {
+                None
+            },
+        };
+
         if unsafe { sys::yaml_emitter_emit(&mut self.inner, &mut event.into_raw()?) }.ok {
             debug_assert!(self.writer_error.is_none());
+
+            if let Some(validator) = &mut self.validator {
+                validator.commit(kind, anchor);
+            }
+
+            Ok(())
+        } else {
+            self.failed = true;
+
+            match self.writer_error.take() {
+                Some(e) => Err(EmitterError::IoError(e)),
+                None => Err(EmitterError::from_raw(&self.inner, Some(kind))),
+            }
+        }
+    }
+
+    /// Dump a document.
+    ///
+    /// The stream is started automatically by the first call; use [`close`]
+    /// to finish it.  Calls to this method must not be mixed with calls to
+    /// [`emit`] on the same emitter.
+    ///
+    /// ```
+    /// # use libyaml::*;
+    /// #
+    /// # fn doctest() -> Result<(), Box<dyn std::error::Error>> {
+    /// let mut parser = Parser::from_str("a: 1\n--- [x, y]\n")?;
+    /// let mut output = Vec::new();
+    /// let mut emitter = Emitter::new(&mut output)?;
+    ///
+    /// while let Some(document) = parser.load()? {
+    ///     emitter.dump(document)?;
+    /// }
+    ///
+    /// emitter.close()?;
+    /// drop(emitter);
+    ///
+    /// assert_eq!(String::from_utf8(output)?, "a: 1\n--- [x, y]\n");
+    /// # Ok(())
+    /// # }
+    /// # doctest().unwrap();
+    /// ```
+    ///
+    /// [`close`]: #method.close
+    /// [`emit`]: #method.emit
+    pub fn dump(&mut self, document: Document) -> Result<(), EmitterError> {
+        self.open()?;
+
+        if unsafe { sys::yaml_emitter_dump(&mut self.inner, &mut document.into_raw()) }.ok {
+            debug_assert!(self.writer_error.is_none());
+            Ok(())
+        } else {
+            match self.writer_error.take() {
+                Some(e) => Err(EmitterError::IoError(e)),
+                None => Err(EmitterError::from_raw(&self.inner, None)),
+            }
+        }
+    }
+
+    /// Finish a stream of documents written by [`dump`].
+    ///
+    /// An emitter whose stream was started with [`emit`] cannot be closed
+    /// this way; an error is returned instead.
+    ///
+    /// ```
+    /// # use libyaml::*;
+    /// #
+    /// # fn doctest() -> Result<(), EmitterError> {
+    /// let mut emitter = Emitter::new(Vec::new())?;
+    /// emitter.emit(Event::StreamStart { encoding: None })?;
+    ///
+    /// let document = Document::new(None, vec![], true, true).unwrap();
+    /// assert!(emitter.dump(document).is_err());
+    /// assert!(emitter.close().is_err());
+    /// # Ok(())
+    /// # }
+    /// # doctest().unwrap();
+    /// ```
+    ///
+    /// [`emit`]: #method.emit
+    /// [`dump`]: #method.dump
+    pub fn close(&mut self) -> Result<(), EmitterError> {
+        self.open()?;
+
+        if unsafe { sys::yaml_emitter_close(&mut self.inner) }.ok {
+            debug_assert!(self.writer_error.is_none());
             Ok(())
         } else {
-            match mem::replace(&mut self.writer_error, None) {
+            match self.writer_error.take() {
                 Some(e) => Err(EmitterError::IoError(e)),
-                None => Err(EmitterError::LibYamlError),
+                None => Err(EmitterError::from_raw(&self.inner, None)),
+            }
+        }
+    }
+
+    fn open(&mut self) -> Result<(), EmitterError> {
+        if self.opened {
+            Ok(())
+        } else if unsafe { sys::yaml_emitter_open(&mut self.inner) }.ok {
+            debug_assert!(self.writer_error.is_none());
+            self.opened = true;
+            Ok(())
+        } else {
+            match self.writer_error.take() {
+                Some(e) => Err(EmitterError::IoError(e)),
+                None => Err(EmitterError::from_raw(&self.inner, None)),
             }
         }
     }
@@ -62,10 +273,17 @@ impl<'a> Emitter<'a> {
         if unsafe { sys::yaml_emitter_flush(&mut self.inner) }.ok {
             Ok(())
         } else {
-            Err(EmitterError::LibYamlError)
+            match self.writer_error.take() {
+                Some(e) => Err(EmitterError::IoError(e)),
+                None => Err(EmitterError::from_raw(&self.inner, None)),
+            }
         }
     }
 
+    pub(crate) fn replace_writer(&mut self, writer: Box<dyn io::Write + 'a>) -> Box<dyn io::Write + 'a> {
+        mem::replace(&mut self.writer, writer)
+    }
+
     /// Return raw pointer to the underlying `yaml_emitter_t`.
     pub fn as_raw_ptr(&mut self) -> *mut sys::yaml_emitter_t {
         &mut self.inner
@@ -1,6 +1,6 @@
 use std::io;
 
-use crate::{Emitter, EmitterError, Encoding, LineBreak};
+use crate::{Emitter, EmitterError, Encoding, EventValidator, LineBreak};
 use crate::sys;
 
 /// Builder for emitters.
@@ -79,6 +79,15 @@ impl<'a> EmitterBuilder<'a> {
         self
     }
 
+    /// Enable or disable checking emitted events with an [`EventValidator`].
+    /// Disabled by default.
+    ///
+    /// [`EventValidator`]: struct.EventValidator.html
+    pub fn validate(mut self, enable: bool) -> Self {
+        self.emitter.validator = if enable { Some(EventValidator::new()) } else { None };
+        self
+    }
+
     /// Enable or disable unescaped non-ASCII characters in output.
     pub fn unicode(mut self, enable: bool) -> Self {
         unsafe {
@@ -1,8 +1,10 @@
 use std::error;
+use std::ffi;
 use std::fmt;
 use std::io;
 
-use crate::EventError;
+use crate::{ErrorKind, EventError, EventKind, ValidationError};
+use crate::sys;
 
 /// Error returned from [`Emitter`] methods.
 ///
@@ -13,18 +15,91 @@ pub enum EmitterError {
     IoError(io::Error),
 
     /// LibYAML error.
-    LibYamlError,
+    LibYamlError {
+        /// Error kind.
+        kind: ErrorKind,
+
+        /// Error description.
+        problem: Option<String>,
+
+        /// Kind of the event passed to the failed [`Emitter::emit`] call, or
+        /// `None` if the error was not caused by emitting an event.
+        ///
+        /// LibYAML buffers a few events before writing them out, so the
+        /// problem may have been caused by one of the recently emitted events.
+        ///
+        /// [`Emitter::emit`]: struct.Emitter.html#method.emit
+        event: Option<EventKind>,
+    },
+
+    /// Event could not be converted for LibYAML.
+    EventError(EventError),
+
+    /// Event rejected by the [`EventValidator`].
+    ///
+    /// [`EventValidator`]: struct.EventValidator.html
+    ValidationError(ValidationError),
+
+    /// Emitter failed on an earlier event and cannot emit any more.
+    Failed,
+}
+
+impl EmitterError {
+    /// Extract the error reported by a raw `yaml_emitter_t`, attributing it to
+    /// an event of the given kind.
+    pub fn from_raw(raw: &sys::yaml_emitter_t, event: Option<EventKind>) -> Self {
+        Self::LibYamlError {
+            kind: ErrorKind::from_raw(raw.error).unwrap_or(ErrorKind::Emitter),
+            problem: if raw.problem.is_null() {
+                None
+            } else {
+                Some(unsafe { ffi::CStr::from_ptr(raw.problem) }.to_string_lossy().into_owned())
+            },
+            event,
+        }
+    }
 }
 
 impl From<EventError> for EmitterError {
-    fn from(_: EventError) -> Self {
-        Self::LibYamlError
+    fn from(event_error: EventError) -> Self {
+        Self::EventError(event_error)
+    }
+}
+
+impl From<ValidationError> for EmitterError {
+    fn from(validation_error: ValidationError) -> Self {
+        Self::ValidationError(validation_error)
     }
 }
 
 impl fmt::Display for EmitterError {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "YAML emitter error")
+        match self {
+            Self::IoError(io_error) => {
+                write!(f, "I/O error: {}", io_error)
+            },
+            Self::LibYamlError { kind, problem, event } => {
+                match problem {
+                    Some(problem) => write!(f, "{}", problem)?,
+                    None => write!(f, "YAML {}", kind)?,
+                }
+
+                if let Some(event) = event {
+                    write!(f, " while emitting {} event", event)?;
+                }
+
+                Ok(())
+            },
+            Self::EventError(event_error) => {
+                write!(f, "invalid YAML event: {}", event_error)
+            },
+            Self::ValidationError(validation_error) => {
+                write!(f, "ill-formed event stream: {}", validation_error)
+            },
+            Self::Failed => {
+                write!(f, "emitter failed on an earlier event")
+            },
+        }
     }
 }
 
@@ -32,7 +107,10 @@ impl error::Error for EmitterError {
     fn source(&self) -> Option<&(dyn error::Error + 'static)> {
         match self {
             Self::IoError(io_error) => Some(io_error),
-            Self::LibYamlError => None,
+            Self::LibYamlError { .. } => None,
+            Self::EventError(event_error) => Some(event_error),
+            Self::ValidationError(validation_error) => Some(validation_error),
+            Self::Failed => None,
         }
     }
 }
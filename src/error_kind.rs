@@ -0,0 +1,72 @@
+use std::fmt;
+
+use crate::sys;
+
+/// Kind of an error reported by LibYAML.
+#[derive(Copy, Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+#[repr(u8)]
+pub enum ErrorKind {
+    /// Cannot allocate or reallocate a block of memory.
+    Memory = sys::YAML_MEMORY_ERROR as _,
+
+    /// Cannot read or decode the input stream.
+    Reader = sys::YAML_READER_ERROR as _,
+
+    /// Cannot scan the input stream.
+    Scanner = sys::YAML_SCANNER_ERROR as _,
+
+    /// Cannot parse the input stream.
+    Parser = sys::YAML_PARSER_ERROR as _,
+
+    /// Cannot compose a YAML document.
+    Composer = sys::YAML_COMPOSER_ERROR as _,
+
+    /// Cannot write to the output stream.
+    Writer = sys::YAML_WRITER_ERROR as _,
+
+    /// Cannot emit a YAML stream.
+    Emitter = sys::YAML_EMITTER_ERROR as _,
+}
+
+impl ErrorKind {
+    /// Convert from `yaml_error_type_t`; `YAML_NO_ERROR` becomes `None`.
+    pub fn from_raw(raw: sys::yaml_error_type_t) -> Option<Self> {
+        match raw {
+            sys::YAML_MEMORY_ERROR => Some(Self::Memory),
+            sys::YAML_READER_ERROR => Some(Self::Reader),
+            sys::YAML_SCANNER_ERROR => Some(Self::Scanner),
+            sys::YAML_PARSER_ERROR => Some(Self::Parser),
+            sys::YAML_COMPOSER_ERROR => Some(Self::Composer),
+            sys::YAML_WRITER_ERROR => Some(Self::Writer),
+            sys::YAML_EMITTER_ERROR => Some(Self::Emitter),
+            _ => None,
+        }
+    }
+
+    /// Convert to `yaml_error_type_t`.
+    pub fn into_raw(self) -> sys::yaml_error_type_t {
+        match self {
+            Self::Memory => sys::YAML_MEMORY_ERROR,
+            Self::Reader => sys::YAML_READER_ERROR,
+            Self::Scanner => sys::YAML_SCANNER_ERROR,
+            Self::Parser => sys::YAML_PARSER_ERROR,
+            Self::Composer => sys::YAML_COMPOSER_ERROR,
+            Self::Writer => sys::YAML_WRITER_ERROR,
+            Self::Emitter => sys::YAML_EMITTER_ERROR,
+        }
+    }
+}
+
+impl fmt::Display for ErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            Self::Memory => "memory error",
+            Self::Reader => "reader error",
+            Self::Scanner => "scanner error",
+            Self::Parser => "parser error",
+            Self::Composer => "composer error",
+            Self::Writer => "writer error",
+            Self::Emitter => "emitter error",
+        })
+    }
+}
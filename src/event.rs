@@ -2,8 +2,9 @@ use std::ffi;
 use std::mem;
 use std::os::raw;
 use std::ptr;
+use std::slice;
 
-use crate::{Encoding, EventError, MappingStyle, ScalarStyle, SequenceStyle};
+use crate::{Encoding, EventError, EventField, EventKind, EventRef, MappingStyle, ScalarStyle, SequenceStyle};
 use crate::{TagDirective, VersionDirective};
 use crate::sys;
 
@@ -51,7 +52,8 @@ pub enum Event {
         /// Optional tag name.
         tag: Option<String>,
 
-        /// Scalar value.
+        /// Scalar value.  May contain any character, including NUL; the
+        /// emitter escapes characters in the double-quoted style as needed.
         value: String,
 
         /// If true, no tag will be emitted for the plain style.
@@ -102,8 +104,29 @@ pub enum Event {
 }
 
 impl Event {
+    /// Return the kind of this event.
+    pub fn kind(&self) -> EventKind {
+        match self {
+            Self::StreamStart { .. } => EventKind::StreamStart,
+            Self::StreamEnd => EventKind::StreamEnd,
+            Self::DocumentStart { .. } => EventKind::DocumentStart,
+            Self::DocumentEnd { .. } => EventKind::DocumentEnd,
+            Self::Alias { .. } => EventKind::Alias,
+            Self::Scalar { .. } => EventKind::Scalar,
+            Self::SequenceStart { .. } => EventKind::SequenceStart,
+            Self::SequenceEnd => EventKind::SequenceEnd,
+            Self::MappingStart { .. } => EventKind::MappingStart,
+            Self::MappingEnd => EventKind::MappingEnd,
+        }
+    }
+
     /// Take ownership of a raw `yaml_event_t`.  This method frees the allocated
     /// memory, even if the conversion fails.
+    ///
+    /// Strings that are not valid UTF-8 are converted lossily; use
+    /// [`from_raw_strict`] to get an error instead.
+    ///
+    /// [`from_raw_strict`]: #method.from_raw_strict
     pub fn from_raw(mut raw: sys::yaml_event_t) -> Result<Self, EventError> {
         fn from_raw_cstr(ptr: *const raw::c_char) -> Option<String> {
             if ptr.is_null() {
@@ -165,7 +188,10 @@ impl Event {
                     Ok(Self::Scalar {
                         anchor: from_raw_cstr(raw.data.scalar.anchor as *const _),
                         tag: from_raw_cstr(raw.data.scalar.tag as *const _),
-                        value: from_raw_cstr_non_null(raw.data.scalar.value as *const _),
+                        value: String::from_utf8_lossy(slice::from_raw_parts(
+                            raw.data.scalar.value,
+                            raw.data.scalar.length as _,
+                        )).into_owned(),
                         plain_implicit: raw.data.scalar.plain_implicit,
                         quoted_implicit: raw.data.scalar.quoted_implicit,
                         style: ScalarStyle::from_raw(raw.data.scalar.style),
@@ -194,7 +220,7 @@ impl Event {
                     Ok(Self::MappingEnd)
                 },
                 _ => {
-                    Err(EventError)
+                    Err(EventError::UnknownEventType)
                 },
             }
         };
@@ -206,9 +232,35 @@ impl Event {
         ret
     }
 
+    /// Take ownership of a raw `yaml_event_t`, failing with
+    /// [`EventError::InvalidUtf8`] if a string is not valid UTF-8.  This method
+    /// frees the allocated memory, even if the conversion fails.
+    ///
+    /// [`EventError::InvalidUtf8`]: enum.EventError.html#variant.InvalidUtf8
+    pub fn from_raw_strict(mut raw: sys::yaml_event_t) -> Result<Self, EventError> {
+        let ret = EventRef::from_raw(&raw).map(|event| event.to_owned());
+
+        unsafe {
+            sys::yaml_event_delete(&mut raw);
+        }
+
+        ret
+    }
+
     /// Return the raw `yaml_event_t` for this event.  The caller is responsible
     /// for freeing memory.
     pub fn into_raw(self) -> Result<sys::yaml_event_t, EventError> {
+        fn into_cstring(s: String, field: EventField) -> Result<ffi::CString, EventError> {
+            ffi::CString::new(s).map_err(|e| EventError::InteriorNul {
+                field,
+                position: e.nul_position(),
+            })
+        }
+
+        fn option_into_cstring(s: Option<String>, field: EventField) -> Result<Option<ffi::CString>, EventError> {
+            s.map(|s| into_cstring(s, field)).transpose()
+        }
+
         unsafe {
             let mut event = mem::MaybeUninit::zeroed().assume_init();
 
@@ -233,8 +285,8 @@ impl Event {
                     let mut raw_tags = Vec::new();
 
                     for tag in tags {
-                        raw_handles.push(ffi::CString::new(tag.handle)?);
-                        raw_prefixes.push(ffi::CString::new(tag.prefix)?);
+                        raw_handles.push(into_cstring(tag.handle, EventField::TagDirectiveHandle)?);
+                        raw_prefixes.push(into_cstring(tag.prefix, EventField::TagDirectivePrefix)?);
 
                         let mut raw_tag: sys::yaml_tag_directive_t = mem::MaybeUninit::zeroed().assume_init();
                         raw_tag.handle = raw_handles.last().unwrap().as_ptr() as *mut _;
@@ -257,7 +309,7 @@ impl Event {
                     )
                 },
                 Self::Alias { anchor } => {
-                    let anchor = ffi::CString::new(anchor)?;
+                    let anchor = into_cstring(anchor, EventField::Anchor)?;
 
                     sys::yaml_alias_event_initialize(
                         &mut event,
@@ -265,24 +317,23 @@ impl Event {
                     )
                 },
                 Self::Scalar { anchor, tag, value, plain_implicit, quoted_implicit, style } => {
-                    let anchor = anchor.map(ffi::CString::new).transpose()?;
-                    let tag = tag.map(ffi::CString::new).transpose()?;
-                    let value = ffi::CString::new(value)?;
+                    let anchor = option_into_cstring(anchor, EventField::Anchor)?;
+                    let tag = option_into_cstring(tag, EventField::Tag)?;
 
                     sys::yaml_scalar_event_initialize(
                         &mut event,
                         anchor.as_ref().map_or(ptr::null(), |cs| cs.as_ptr()) as *mut _,
                         tag.as_ref().map_or(ptr::null(), |cs| cs.as_ptr()) as *mut _,
                         value.as_ptr() as *mut _,
-                        value.as_bytes().len() as _,
+                        value.len() as _,
                         plain_implicit as _,
                         quoted_implicit as _,
                         ScalarStyle::option_into_raw(style),
                     )
                 },
                 Self::SequenceStart { anchor, tag, implicit, style } => {
-                    let anchor = anchor.map(ffi::CString::new).transpose()?;
-                    let tag = tag.map(ffi::CString::new).transpose()?;
+                    let anchor = option_into_cstring(anchor, EventField::Anchor)?;
+                    let tag = option_into_cstring(tag, EventField::Tag)?;
 
                     sys::yaml_sequence_start_event_initialize(
                         &mut event,
@@ -298,8 +349,8 @@ impl Event {
                     )
                 },
                 Self::MappingStart { anchor, tag, implicit, style } => {
-                    let anchor = anchor.map(ffi::CString::new).transpose()?;
-                    let tag = tag.map(ffi::CString::new).transpose()?;
+                    let anchor = option_into_cstring(anchor, EventField::Anchor)?;
+                    let tag = option_into_cstring(tag, EventField::Tag)?;
 
                     sys::yaml_mapping_start_event_initialize(
                         &mut event,
@@ -316,7 +367,7 @@ impl Event {
                 },
             };
 
-            if ret.ok { Ok(event) } else { Err(EventError) }
+            if ret.ok { Ok(event) } else { Err(EventError::LibYamlError) }
         }
     }
 }
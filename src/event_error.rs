@@ -1,13 +1,63 @@
+use std::error;
 use std::ffi;
+use std::fmt;
+
+use crate::EventField;
 
 /// Error returned from [`Event`] methods.
 ///
 /// [`Event`]: enum.Event.html
 #[derive(Clone, Debug, Eq, Hash, PartialEq)]
-pub struct EventError;
+pub enum EventError {
+    /// String field contains a NUL byte, which LibYAML cannot represent.
+    InteriorNul {
+        /// Offending field.
+        field: EventField,
+
+        /// Byte offset of the NUL byte within the field.
+        position: usize,
+    },
+
+    /// String field of a raw event is not valid UTF-8.
+    InvalidUtf8 {
+        /// Offending field.
+        field: EventField,
+
+        /// Byte offset of the first invalid byte within the field.
+        position: usize,
+    },
+
+    /// Raw event has an unknown type.
+    UnknownEventType,
+
+    /// LibYAML failed to initialize the raw event.
+    LibYamlError,
+}
 
 impl From<ffi::NulError> for EventError {
-    fn from(_: ffi::NulError) -> Self {
-        Self
+    fn from(nul_error: ffi::NulError) -> Self {
+        Self::InteriorNul { field: EventField::Unknown, position: nul_error.nul_position() }
+    }
+}
+
+impl fmt::Display for EventError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::InteriorNul { field, position } => {
+                write!(f, "{} contains a NUL byte at offset {}", field, position)
+            },
+            Self::InvalidUtf8 { field, position } => {
+                write!(f, "{} contains invalid UTF-8 at offset {}", field, position)
+            },
+            Self::UnknownEventType => {
+                write!(f, "unknown YAML event type")
+            },
+            Self::LibYamlError => {
+                write!(f, "YAML event initialization failed")
+            },
+        }
     }
 }
+
+impl error::Error for EventError {
+}
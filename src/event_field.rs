@@ -0,0 +1,39 @@
+use std::fmt;
+
+/// String field of an [`Event`].
+///
+/// [`Event`]: enum.Event.html
+#[derive(Copy, Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum EventField {
+    /// Anchor name of a node or alias.
+    Anchor,
+
+    /// Tag name of a node.
+    Tag,
+
+    /// Scalar value.
+    Value,
+
+    /// Handle of a document tag directive.
+    TagDirectiveHandle,
+
+    /// Prefix of a document tag directive.
+    TagDirectivePrefix,
+
+    /// Field that is not known, as in errors converted from
+    /// `std::ffi::NulError`.
+    Unknown,
+}
+
+impl fmt::Display for EventField {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            Self::Anchor => "anchor",
+            Self::Tag => "tag",
+            Self::Value => "scalar value",
+            Self::TagDirectiveHandle => "tag directive handle",
+            Self::TagDirectivePrefix => "tag directive prefix",
+            Self::Unknown => "string field",
+        })
+    }
+}
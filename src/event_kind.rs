@@ -0,0 +1,54 @@
+use std::fmt;
+
+/// Kind of an [`Event`], without its data.
+///
+/// [`Event`]: enum.Event.html
+#[derive(Copy, Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum EventKind {
+    /// A *STREAM-START* event.
+    StreamStart,
+
+    /// A *STREAM-END* event.
+    StreamEnd,
+
+    /// A *DOCUMENT-START* event.
+    DocumentStart,
+
+    /// A *DOCUMENT-END* event.
+    DocumentEnd,
+
+    /// An *ALIAS* event.
+    Alias,
+
+    /// A *SCALAR* event.
+    Scalar,
+
+    /// A *SEQUENCE-START* event.
+    SequenceStart,
+
+    /// A *SEQUENCE-END* event.
+    SequenceEnd,
+
+    /// A *MAPPING-START* event.
+    MappingStart,
+
+    /// A *MAPPING-END* event.
+    MappingEnd,
+}
+
+impl fmt::Display for EventKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            Self::StreamStart => "STREAM-START",
+            Self::StreamEnd => "STREAM-END",
+            Self::DocumentStart => "DOCUMENT-START",
+            Self::DocumentEnd => "DOCUMENT-END",
+            Self::Alias => "ALIAS",
+            Self::Scalar => "SCALAR",
+            Self::SequenceStart => "SEQUENCE-START",
+            Self::SequenceEnd => "SEQUENCE-END",
+            Self::MappingStart => "MAPPING-START",
+            Self::MappingEnd => "MAPPING-END",
+        })
+    }
+}
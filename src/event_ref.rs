@@ -0,0 +1,296 @@
+use std::ffi;
+use std::os::raw;
+use std::slice;
+use std::str;
+
+use crate::{Encoding, Event, EventError, EventField, EventKind, MappingStyle, ScalarStyle, SequenceStyle};
+use crate::{TagDirective, VersionDirective};
+use crate::sys;
+
+/// Parser event borrowing its strings from the underlying LibYAML event.
+///
+/// Returned by [`Parser::parse_ref`]; the borrowed strings stay valid until
+/// the next call to a parsing method.  Apart from tag directives, converting a
+/// raw event into an `EventRef` does not allocate.
+///
+/// ```
+/// # use libyaml::*;
+/// #
+/// # fn doctest() -> Result<(), ParserError> {
+/// let mut parser = Parser::from_str("[a, bb, ccc]")?;
+/// let mut total = 0;
+///
+/// loop {
+///     match parser.parse_ref()? {
+///         EventRef::Scalar { value, .. } => total += value.len(),
+///         EventRef::StreamEnd => break,
+///         _ => {},
+///     }
+/// }
+///
+/// assert_eq!(total, 6);
+/// # Ok(())
+/// # }
+/// # doctest().unwrap();
+/// ```
+///
+/// [`Parser::parse_ref`]: struct.Parser.html#method.parse_ref
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub enum EventRef<'p> {
+    /// A *STREAM-START* event.
+    StreamStart {
+        /// Stream encoding.
+        encoding: Option<Encoding>,
+    },
+
+    /// A *STREAM-END* event.
+    StreamEnd,
+
+    /// A *DOCUMENT-START* event.
+    DocumentStart {
+        /// Optional version directive.
+        version: Option<VersionDirective>,
+
+        /// List of tag directives, can be empty.
+        tags: Vec<TagDirective>,
+
+        /// If true, the document has no start marker.
+        implicit: bool,
+    },
+
+    /// A *DOCUMENT-END* event.
+    DocumentEnd {
+        /// If true, the document has no end marker.
+        implicit: bool,
+    },
+
+    /// An *ALIAS* event.
+    Alias {
+        /// Target anchor name.
+        anchor: &'p str,
+    },
+
+    /// A *SCALAR* event.
+    Scalar {
+        /// Optional anchor name.
+        anchor: Option<&'p str>,
+
+        /// Optional tag name.
+        tag: Option<&'p str>,
+
+        /// Scalar value.
+        value: &'p str,
+
+        /// If true, the scalar is plain and has no tag.
+        plain_implicit: bool,
+
+        /// If true, the scalar is not plain and has no tag.
+        quoted_implicit: bool,
+
+        /// Scalar style.
+        style: Option<ScalarStyle>,
+    },
+
+    /// A *SEQUENCE-START* event.
+    SequenceStart {
+        /// Optional anchor name.
+        anchor: Option<&'p str>,
+
+        /// Optional tag name.
+        tag: Option<&'p str>,
+
+        /// If true, the sequence has no tag.
+        implicit: bool,
+
+        /// Sequence style.
+        style: Option<SequenceStyle>,
+    },
+
+    /// A *SEQUENCE-END* event.
+    SequenceEnd,
+
+    /// A *MAPPING-START* event.
+    MappingStart {
+        /// Optional anchor name.
+        anchor: Option<&'p str>,
+
+        /// Optional tag name.
+        tag: Option<&'p str>,
+
+        /// If true, the mapping has no tag.
+        implicit: bool,
+
+        /// Mapping style.
+        style: Option<MappingStyle>,
+    },
+
+    /// A *MAPPING-END* event.
+    MappingEnd,
+}
+
+impl<'p> EventRef<'p> {
+    /// Return the kind of this event.
+    pub fn kind(&self) -> EventKind {
+        match self {
+            Self::StreamStart { .. } => EventKind::StreamStart,
+            Self::StreamEnd => EventKind::StreamEnd,
+            Self::DocumentStart { .. } => EventKind::DocumentStart,
+            Self::DocumentEnd { .. } => EventKind::DocumentEnd,
+            Self::Alias { .. } => EventKind::Alias,
+            Self::Scalar { .. } => EventKind::Scalar,
+            Self::SequenceStart { .. } => EventKind::SequenceStart,
+            Self::SequenceEnd => EventKind::SequenceEnd,
+            Self::MappingStart { .. } => EventKind::MappingStart,
+            Self::MappingEnd => EventKind::MappingEnd,
+        }
+    }
+
+    /// Borrow the contents of a raw `yaml_event_t`.  The raw event keeps
+    /// ownership of its memory.
+    pub fn from_raw(raw: &'p sys::yaml_event_t) -> Result<Self, EventError> {
+        unsafe fn from_raw_cstr<'p>(ptr: *const raw::c_char, field: EventField) -> Result<Option<&'p str>, EventError> {
+            if ptr.is_null() {
+                Ok(None)
+            } else {
+                from_raw_cstr_non_null(ptr, field).map(Some)
+            }
+        }
+
+        unsafe fn from_raw_cstr_non_null<'p>(ptr: *const raw::c_char, field: EventField) -> Result<&'p str, EventError> {
+            from_utf8(ffi::CStr::from_ptr(ptr).to_bytes(), field)
+        }
+
+        fn from_utf8(bytes: &[u8], field: EventField) -> Result<&str, EventError> {
+            str::from_utf8(bytes).map_err(|e| EventError::InvalidUtf8 { field, position: e.valid_up_to() })
+        }
+
+        unsafe {
+            match raw.type_ {
+                sys::YAML_STREAM_START_EVENT => {
+                    Ok(Self::StreamStart {
+                        encoding: Encoding::from_raw(raw.data.stream_start.encoding),
+                    })
+                },
+                sys::YAML_STREAM_END_EVENT => {
+                    Ok(Self::StreamEnd)
+                },
+                sys::YAML_DOCUMENT_START_EVENT => {
+                    let version_ptr = raw.data.document_start.version_directive;
+                    let mut tag_start_ptr = raw.data.document_start.tag_directives.start;
+                    let tag_end_ptr = raw.data.document_start.tag_directives.end;
+                    let mut tags = Vec::new();
+
+                    while tag_start_ptr != tag_end_ptr {
+                        tags.push(TagDirective {
+                            handle: from_raw_cstr_non_null((*tag_start_ptr).handle as *const _, EventField::TagDirectiveHandle)?.to_string(),
+                            prefix: from_raw_cstr_non_null((*tag_start_ptr).prefix as *const _, EventField::TagDirectivePrefix)?.to_string(),
+                        });
+                        tag_start_ptr = tag_start_ptr.offset(1);
+                    }
+
+                    Ok(Self::DocumentStart {
+                        version: if version_ptr.is_null() {
+                            None
+                        } else {
+                            Some(VersionDirective::from_raw(*version_ptr))
+                        },
+                        tags,
+                        implicit: raw.data.document_start.implicit,
+                    })
+                },
+                sys::YAML_DOCUMENT_END_EVENT => {
+                    Ok(Self::DocumentEnd {
+                        implicit: raw.data.document_end.implicit,
+                    })
+                },
+                sys::YAML_ALIAS_EVENT => {
+                    Ok(Self::Alias {
+                        anchor: from_raw_cstr_non_null(raw.data.alias.anchor as *const _, EventField::Anchor)?,
+                    })
+                },
+                sys::YAML_SCALAR_EVENT => {
+                    let value = slice::from_raw_parts(raw.data.scalar.value, raw.data.scalar.length as _);
+
+                    Ok(Self::Scalar {
+                        anchor: from_raw_cstr(raw.data.scalar.anchor as *const _, EventField::Anchor)?,
+                        tag: from_raw_cstr(raw.data.scalar.tag as *const _, EventField::Tag)?,
+                        value: from_utf8(value, EventField::Value)?,
+                        plain_implicit: raw.data.scalar.plain_implicit,
+                        quoted_implicit: raw.data.scalar.quoted_implicit,
+                        style: ScalarStyle::from_raw(raw.data.scalar.style),
+                    })
+                },
+                sys::YAML_SEQUENCE_START_EVENT => {
+                    Ok(Self::SequenceStart {
+                        anchor: from_raw_cstr(raw.data.sequence_start.anchor as *const _, EventField::Anchor)?,
+                        tag: from_raw_cstr(raw.data.sequence_start.tag as *const _, EventField::Tag)?,
+                        implicit: raw.data.sequence_start.implicit,
+                        style: SequenceStyle::from_raw(raw.data.sequence_start.style),
+                    })
+                },
+                sys::YAML_SEQUENCE_END_EVENT => {
+                    Ok(Self::SequenceEnd)
+                },
+                sys::YAML_MAPPING_START_EVENT => {
+                    Ok(Self::MappingStart {
+                        anchor: from_raw_cstr(raw.data.mapping_start.anchor as *const _, EventField::Anchor)?,
+                        tag: from_raw_cstr(raw.data.mapping_start.tag as *const _, EventField::Tag)?,
+                        implicit: raw.data.mapping_start.implicit,
+                        style: MappingStyle::from_raw(raw.data.mapping_start.style),
+                    })
+                },
+                sys::YAML_MAPPING_END_EVENT => {
+                    Ok(Self::MappingEnd)
+                },
+                _ => {
+                    Err(EventError::UnknownEventType)
+                },
+            }
+        }
+    }
+
+    /// Convert into an owned [`Event`].
+    ///
+    /// [`Event`]: enum.Event.html
+    pub fn to_owned(&self) -> Event {
+        match self.clone() {
+            Self::StreamStart { encoding } => {
+                Event::StreamStart { encoding }
+            },
+            Self::StreamEnd => {
+                Event::StreamEnd
+            },
+            Self::DocumentStart { version, tags, implicit } => {
+                Event::DocumentStart { version, tags, implicit }
+            },
+            Self::DocumentEnd { implicit } => {
+                Event::DocumentEnd { implicit }
+            },
+            Self::Alias { anchor } => {
+                Event::Alias { anchor: anchor.to_string() }
+            },
+            Self::Scalar { anchor, tag, value, plain_implicit, quoted_implicit, style } => {
+                Event::Scalar {
+                    anchor: anchor.map(str::to_string),
+                    tag: tag.map(str::to_string),
+                    value: value.to_string(),
+                    plain_implicit,
+                    quoted_implicit,
+                    style,
+                }
+            },
+            Self::SequenceStart { anchor, tag, implicit, style } => {
+                Event::SequenceStart { anchor: anchor.map(str::to_string), tag: tag.map(str::to_string), implicit, style }
+            },
+            Self::SequenceEnd => {
+                Event::SequenceEnd
+            },
+            Self::MappingStart { anchor, tag, implicit, style } => {
+                Event::MappingStart { anchor: anchor.map(str::to_string), tag: tag.map(str::to_string), implicit, style }
+            },
+            Self::MappingEnd => {
+                Event::MappingEnd
+            },
+        }
+    }
+}
@@ -0,0 +1,194 @@
+use std::collections::HashSet;
+
+use crate::{Event, EventKind, ValidationError};
+
+const NODE: &[EventKind] = &[
+    EventKind::Alias,
+    EventKind::Scalar,
+    EventKind::SequenceStart,
+    EventKind::MappingStart,
+];
+
+const NODE_OR_SEQUENCE_END: &[EventKind] = &[
+    EventKind::Alias,
+    EventKind::Scalar,
+    EventKind::SequenceStart,
+    EventKind::MappingStart,
+    EventKind::SequenceEnd,
+];
+
+const NODE_OR_MAPPING_END: &[EventKind] = &[
+    EventKind::Alias,
+    EventKind::Scalar,
+    EventKind::SequenceStart,
+    EventKind::MappingStart,
+    EventKind::MappingEnd,
+];
+
+/// State machine checking that a sequence of events forms a well-formed YAML
+/// stream.
+///
+/// The validator checks the ordering of stream, document and collection
+/// events, that every mapping key has a value, that aliases refer to anchors
+/// defined earlier in the same document, and that no anchor is defined twice
+/// in a document.  It can be used standalone, or built into an [`Emitter`]
+/// with [`EmitterBuilder::validate`].  A rejected event does not change the
+/// state of the validator.
+///
+/// ```
+/// # use libyaml::*;
+/// #
+/// let mut validator = EventValidator::new();
+/// assert!(validator.validate(&Event::StreamStart { encoding: None }).is_ok());
+/// assert_eq!(
+///     validator.validate(&Event::MappingEnd),
+///     Err(ValidationError::UnexpectedEvent {
+///         event: EventKind::MappingEnd,
+///         expected: &[EventKind::DocumentStart, EventKind::StreamEnd],
+///         index: 1,
+///     }),
+/// );
+/// ```
+///
+/// [`Emitter`]: struct.Emitter.html
+/// [`EmitterBuilder::validate`]: struct.EmitterBuilder.html#method.validate
+#[derive(Clone, Debug)]
+pub struct EventValidator {
+    states: Vec<State>,
+    anchors: HashSet<String>,
+    index: usize,
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+enum State {
+    StreamStart,
+    Stream,
+    DocumentContent,
+    DocumentEnd,
+    Sequence,
+    MappingKey,
+    MappingValue,
+}
+
+impl EventValidator {
+    /// Create a validator expecting the start of a stream.
+    pub fn new() -> Self {
+        Self { states: vec![State::StreamStart], anchors: HashSet::new(), index: 0 }
+    }
+
+    /// Check the next event of the stream.
+    pub fn validate(&mut self, event: &Event) -> Result<(), ValidationError> {
+        self.check(event)?;
+        self.commit(event.kind(), node_anchor(event).cloned());
+        Ok(())
+    }
+
+    /// Check the next event of the stream without accepting it.
+    pub(crate) fn check(&self, event: &Event) -> Result<(), ValidationError> {
+        let index = self.index;
+        let kind = event.kind();
+        let state = self.states.last().copied();
+
+        let expected = match state {
+            Some(State::StreamStart) => &[EventKind::StreamStart][..],
+            Some(State::Stream) => &[EventKind::DocumentStart, EventKind::StreamEnd][..],
+            Some(State::DocumentContent) => NODE,
+            Some(State::DocumentEnd) => &[EventKind::DocumentEnd][..],
+            Some(State::Sequence) => NODE_OR_SEQUENCE_END,
+            Some(State::MappingKey) => NODE_OR_MAPPING_END,
+            Some(State::MappingValue) => NODE,
+            None => &[][..],
+        };
+
+        if state == Some(State::MappingValue) && kind == EventKind::MappingEnd {
+            return Err(ValidationError::MissingValue { index });
+        }
+
+        if !expected.contains(&kind) {
+            return Err(ValidationError::UnexpectedEvent { event: kind, expected, index });
+        }
+
+        match event {
+            Event::Alias { anchor } if !self.anchors.contains(anchor) => {
+                Err(ValidationError::UndefinedAlias { anchor: anchor.clone(), index })
+            },
+            _ => {
+                match node_anchor(event) {
+                    Some(anchor) if self.anchors.contains(anchor) => {
+                        Err(ValidationError::DuplicateAnchor { anchor: anchor.clone(), index })
+                    },
+                    _ => {
+                        Ok(())
+                    },
+                }
+            },
+        }
+    }
+
+    /// Accept an event that passed `check`.
+    pub(crate) fn commit(&mut self, kind: EventKind, anchor: Option<String>) {
+        let state = self.states.last().copied();
+
+        if let Some(anchor) = anchor {
+            self.anchors.insert(anchor);
+        }
+
+        match kind {
+            EventKind::StreamStart => {
+                self.states[0] = State::Stream;
+            },
+            EventKind::DocumentStart => {
+                self.anchors.clear();
+                self.states.push(State::DocumentEnd);
+                self.states.push(State::DocumentContent);
+            },
+            EventKind::StreamEnd | EventKind::DocumentEnd | EventKind::SequenceEnd | EventKind::MappingEnd => {
+                self.states.pop();
+            },
+            EventKind::Alias | EventKind::Scalar | EventKind::SequenceStart | EventKind::MappingStart => {
+                match state {
+                    Some(State::DocumentContent) => {
+                        self.states.pop();
+                    },
+                    Some(State::MappingKey) => {
+                        *self.states.last_mut().unwrap() = State::MappingValue;
+                    },
+                    Some(State::MappingValue) => {
+                        *self.states.last_mut().unwrap() = State::MappingKey;
+                    },
+                    _ => {},
+                }
+
+                match kind {
+                    EventKind::SequenceStart => self.states.push(State::Sequence),
+                    EventKind::MappingStart => self.states.push(State::MappingKey),
+                    _ => {},
+                }
+            },
+        }
+
+        self.index += 1;
+    }
+
+    /// Check whether the stream has ended.
+    pub fn is_finished(&self) -> bool {
+        self.states.is_empty()
+    }
+}
+
+impl Default for EventValidator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+pub(crate) fn node_anchor(event: &Event) -> Option<&String> {
+    match event {
+        Event::Scalar { anchor, .. } | Event::SequenceStart { anchor, .. } | Event::MappingStart { anchor, .. } => {
+            anchor.as_ref()
+        },
+        _ => {
+            None
+        },
+    }
+}
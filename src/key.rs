@@ -0,0 +1,270 @@
+use std::cmp::Ordering;
+use std::hash::{Hash, Hasher};
+use std::slice;
+
+use crate::{Node, ScalarStyle};
+use crate::tag::{self, Schema};
+
+/// Canonical form of a mapping key, used to compare keys for equality.
+///
+/// Scalars are compared by resolved tag and canonical value under the core
+/// schema, collections structurally; mapping pairs are compared regardless of
+/// their order.  Comparing, hashing and dropping keys walk them with an
+/// explicit stack, so deeply nested keys do not overflow the call stack.
+#[derive(Debug)]
+pub(crate) enum Key {
+    Scalar(String, String),
+    Sequence(String, Vec<Key>),
+    Mapping(String, Vec<(Key, Key)>),
+    Alias(String),
+}
+
+impl Key {
+    pub(crate) fn scalar(tag: Option<&str>, value: &str, style: Option<ScalarStyle>) -> Self {
+        let tag = match tag {
+            Some("!") => tag::STR,
+            Some(tag) => tag,
+            None if style == Some(ScalarStyle::Plain) => Schema::Core.resolve(value),
+            None => tag::STR,
+        };
+
+        let canonical = match tag {
+            tag::NULL => Some(String::new()),
+            tag::BOOL => Schema::Core.parse_bool(value).map(|b| b.to_string()),
+            tag::INT => Schema::Core.parse_int(value).map(|i| i.to_string()),
+            tag::FLOAT => Schema::Core.parse_float(value).map(|f| format!("{:?}", f)),
+            _ => None,
+        };
+
+        Self::Scalar(tag.to_string(), canonical.unwrap_or_else(|| value.to_string()))
+    }
+
+    pub(crate) fn sequence(tag: Option<&str>, items: Vec<Key>) -> Self {
+        Self::Sequence(tag.filter(|tag| *tag != "!").unwrap_or(tag::SEQ).to_string(), items)
+    }
+
+    pub(crate) fn mapping(tag: Option<&str>, mut pairs: Vec<(Key, Key)>) -> Self {
+        pairs.sort();
+        Self::Mapping(tag.filter(|tag| *tag != "!").unwrap_or(tag::MAP).to_string(), pairs)
+    }
+
+    pub(crate) fn from_node(node: &Node) -> Self {
+        enum Frame<'n> {
+            Sequence(Option<&'n str>, slice::Iter<'n, Node>, Vec<Key>),
+            Mapping(Option<&'n str>, slice::Iter<'n, (Node, Node)>, Vec<(Key, Key)>, Option<Key>, Option<&'n Node>),
+        }
+
+        let mut stack = Vec::new();
+        let mut next = Some(node);
+        let mut done = None;
+
+        loop {
+            if let Some(node) = next.take() {
+                match node {
+                    Node::Scalar { tag, value, style, .. } => {
+                        done = Some(Self::scalar(tag.as_deref(), value, *style));
+                    },
+                    Node::Sequence { tag, items, .. } => {
+                        stack.push(Frame::Sequence(tag.as_deref(), items.iter(), Vec::with_capacity(items.len())));
+                    },
+                    Node::Mapping { tag, pairs, .. } => {
+                        stack.push(Frame::Mapping(tag.as_deref(), pairs.iter(), Vec::with_capacity(pairs.len()), None, None));
+                    },
+                    Node::Alias { anchor, .. } => {
+                        done = Some(Self::Alias(anchor.clone()));
+                    },
+                }
+            }
+
+            if let Some(key) = done.take() {
+                match stack.last_mut() {
+                    Some(Frame::Sequence(_, _, items)) => {
+                        items.push(key);
+                    },
+                    Some(Frame::Mapping(_, _, pairs, pending_key, _)) => {
+                        match pending_key.take() {
+                            Some(pending_key) => pairs.push((pending_key, key)),
+                            None => *pending_key = Some(key),
+                        }
+                    },
+                    None => {
+                        return key;
+                    },
+                }
+            }
+
+            match stack.last_mut() {
+                Some(Frame::Sequence(_, items, _)) => {
+                    next = items.next();
+
+                    if next.is_none() {
+                        if let Some(Frame::Sequence(tag, _, items)) = stack.pop() {
+                            done = Some(Self::sequence(tag, items));
+                        }
+                    }
+                },
+                Some(Frame::Mapping(_, pairs, _, _, pending_value)) => {
+                    match pending_value.take() {
+                        Some(value) => {
+                            next = Some(value);
+                        },
+                        None => {
+                            match pairs.next() {
+                                Some((key, value)) => {
+                                    next = Some(key);
+                                    *pending_value = Some(value);
+                                },
+                                None => {
+                                    if let Some(Frame::Mapping(tag, _, pairs, ..)) = stack.pop() {
+                                        done = Some(Self::mapping(tag, pairs));
+                                    }
+                                },
+                            }
+                        },
+                    }
+                },
+                None => {
+                    unreachable!();
+                },
+            }
+        }
+    }
+
+    fn tokens(&self) -> Tokens<'_> {
+        Tokens { next: Some(self), stack: Vec::new() }
+    }
+}
+
+impl Drop for Key {
+    fn drop(&mut self) {
+        fn take_children(key: &mut Key, stack: &mut Vec<Key>) {
+            match key {
+                Key::Sequence(_, items) => {
+                    stack.append(items);
+                },
+                Key::Mapping(_, pairs) => {
+                    for (key, value) in pairs.drain(..) {
+                        stack.push(key);
+                        stack.push(value);
+                    }
+                },
+                _ => {},
+            }
+        }
+
+        let mut stack = Vec::new();
+        take_children(self, &mut stack);
+
+        while let Some(mut key) = stack.pop() {
+            take_children(&mut key, &mut stack);
+        }
+    }
+}
+
+impl Hash for Key {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.tokens().for_each(|token| token.hash(state));
+    }
+}
+
+impl PartialEq for Key {
+    fn eq(&self, other: &Self) -> bool {
+        self.tokens().eq(other.tokens())
+    }
+}
+
+impl Eq for Key {
+}
+
+impl PartialOrd for Key {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Key {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.tokens().cmp(other.tokens())
+    }
+}
+
+/// Element of a key visited in depth-first order.  Collections are closed by
+/// `End`, so equal token sequences mean equal keys.
+#[derive(Eq, Hash, Ord, PartialEq, PartialOrd)]
+enum Token<'k> {
+    Scalar(&'k str, &'k str),
+    Sequence(&'k str),
+    Mapping(&'k str),
+    Alias(&'k str),
+    End,
+}
+
+struct Tokens<'k> {
+    next: Option<&'k Key>,
+    stack: Vec<Children<'k>>,
+}
+
+enum Children<'k> {
+    Items(slice::Iter<'k, Key>),
+    Pairs(slice::Iter<'k, (Key, Key)>, Option<&'k Key>),
+}
+
+impl<'k> Iterator for Tokens<'k> {
+    type Item = Token<'k>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(key) = self.next.take() {
+                return Some(match key {
+                    Key::Scalar(tag, value) => {
+                        Token::Scalar(tag, value)
+                    },
+                    Key::Sequence(tag, items) => {
+                        self.stack.push(Children::Items(items.iter()));
+                        Token::Sequence(tag)
+                    },
+                    Key::Mapping(tag, pairs) => {
+                        self.stack.push(Children::Pairs(pairs.iter(), None));
+                        Token::Mapping(tag)
+                    },
+                    Key::Alias(anchor) => {
+                        Token::Alias(anchor)
+                    },
+                });
+            }
+
+            match self.stack.last_mut()? {
+                Children::Items(items) => {
+                    match items.next() {
+                        Some(item) => {
+                            self.next = Some(item);
+                        },
+                        None => {
+                            self.stack.pop();
+                            return Some(Token::End);
+                        },
+                    }
+                },
+                Children::Pairs(pairs, pending_value) => {
+                    match pending_value.take() {
+                        Some(value) => {
+                            self.next = Some(value);
+                        },
+                        None => {
+                            match pairs.next() {
+                                Some((key, value)) => {
+                                    self.next = Some(key);
+                                    *pending_value = Some(value);
+                                },
+                                None => {
+                                    self.stack.pop();
+                                    return Some(Token::End);
+                                },
+                            }
+                        },
+                    }
+                },
+            }
+        }
+    }
+}
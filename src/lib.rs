@@ -34,7 +34,7 @@
 //! let mut emitter = Emitter::new(writer)?;
 //!
 //! emitter.emit(Event::StreamStart { encoding: None })?;
-//! emitter.emit(Event::DocumentStart { implicit: true })?;
+//! emitter.emit(Event::DocumentStart { version: None, tags: vec![], implicit: true })?;
 //! emitter.emit(Event::Scalar {
 //!     anchor: None,
 //!     tag: Some(tag::INT.to_string()),
@@ -50,43 +50,160 @@
 //! ```
 //!
 //! [`Emitter`]: struct.Emitter.html
+//!
+//! # Serde support
+//!
+//! With the `serde` feature enabled, [`from_reader`], [`from_str`] and
+//! [`from_slice`] deserialize values directly from parser events, and
+//! [`to_writer`], [`to_string`] and [`to_vec`] serialize values as emitter
+//! events.
+//!
+//! # Features
+//!
+//! * `serde` (off by default): enables [`Deserializer`], [`DeserializerError`],
+//!   [`Serializer`], [`SerializerError`] and the functions listed above.
+//!
+//! [`from_reader`]: fn.from_reader.html
+//! [`from_str`]: fn.from_str.html
+//! [`from_slice`]: fn.from_slice.html
+//! [`to_writer`]: fn.to_writer.html
+//! [`to_string`]: fn.to_string.html
+//! [`to_vec`]: fn.to_vec.html
+//! [`Deserializer`]: struct.Deserializer.html
+//! [`DeserializerError`]: enum.DeserializerError.html
+//! [`Serializer`]: struct.Serializer.html
+//! [`SerializerError`]: enum.SerializerError.html
 
 #![allow(clippy::unsafe_removed_from_name)]
 
 pub mod tag;
 
+pub use self::alias_expander::AliasExpander;
+pub use self::comment_emitter::CommentEmitter;
+pub use self::comment_extractor::CommentExtractor;
+pub use self::comments::Comments;
+#[cfg(feature = "serde")]
+pub use self::deserializer::{Deserializer, from_reader, from_slice, from_str};
+#[cfg(feature = "serde")]
+pub use self::deserializer_error::DeserializerError;
+pub use self::document::Document;
+pub use self::document_error::DocumentError;
+pub use self::document_node::DocumentNode;
+pub use self::dumper::Dumper;
+pub use self::duplicate_key_policy::DuplicateKeyPolicy;
+pub use self::duplicate_key_validator::DuplicateKeyValidator;
+pub use self::editor::Editor;
+pub use self::editor_error::EditorError;
 pub use self::emitter::Emitter;
 pub use self::emitter_builder::EmitterBuilder;
 pub use self::emitter_error::EmitterError;
 pub use self::encoding::Encoding;
+pub use self::error_kind::ErrorKind;
 pub use self::event::Event;
 pub use self::event_error::EventError;
+pub use self::event_field::EventField;
+pub use self::event_kind::EventKind;
+pub use self::event_ref::EventRef;
+pub use self::event_validator::EventValidator;
+pub use self::limit::Limit;
 pub use self::line_break::LineBreak;
+pub use self::loader::Loader;
 pub use self::mapping_style::MappingStyle;
+pub use self::mark::Mark;
+pub use self::merge_error::MergeError;
+pub use self::node::Node;
+pub use self::node_id::NodeId;
+pub use self::owned_emitter::OwnedEmitter;
 pub use self::parser::Parser;
 pub use self::parser_builder::ParserBuilder;
 pub use self::parser_error::ParserError;
 pub use self::parser_iter::ParserIter;
+pub use self::parser_spanned_iter::ParserSpannedIter;
+pub use self::path::Path;
+pub use self::path_error::PathError;
+pub use self::path_segment::PathSegment;
+pub use self::path_tracker::PathTracker;
+pub use self::query::Query;
+pub use self::query_matcher::QueryMatcher;
+pub use self::query_segment::QuerySegment;
 pub use self::scalar_style::ScalarStyle;
+pub use self::scanner::Scanner;
 pub use self::sequence_style::SequenceStyle;
+#[cfg(feature = "serde")]
+pub use self::serializer::{Serializer, to_string, to_vec, to_writer};
+#[cfg(feature = "serde")]
+pub use self::serializer_error::SerializerError;
+pub use self::span::Span;
 pub use self::tag_directive::TagDirective;
+pub use self::tag_resolver::TagResolver;
+pub use self::tag_resolver_error::TagResolverError;
+pub use self::token::Token;
+pub use self::validation_error::ValidationError;
 pub use self::version_directive::VersionDirective;
 
+mod alias_expander;
+mod comment_emitter;
+mod comment_extractor;
+mod comments;
+#[cfg(feature = "serde")]
+mod deserializer;
+#[cfg(feature = "serde")]
+mod deserializer_error;
+mod document;
+mod document_error;
+mod document_node;
+mod dumper;
+mod duplicate_key_policy;
+mod duplicate_key_validator;
+mod editor;
+mod editor_error;
 mod emitter;
 mod emitter_builder;
 mod emitter_error;
 mod encoding;
+mod error_kind;
 mod event;
 mod event_error;
+mod event_field;
+mod event_kind;
+mod event_ref;
+mod event_validator;
+mod key;
+mod limit;
 mod line_break;
+mod loader;
 mod mapping_style;
+mod mark;
+mod merge_error;
+mod node;
+mod node_id;
+mod owned_emitter;
 mod parser;
 mod parser_builder;
 mod parser_error;
 mod parser_iter;
+mod parser_spanned_iter;
+mod path;
+mod path_error;
+mod path_segment;
+mod path_tracker;
+mod query;
+mod query_matcher;
+mod query_segment;
 mod scalar_style;
+mod scanner;
 mod sequence_style;
+#[cfg(feature = "serde")]
+mod serializer;
+#[cfg(feature = "serde")]
+mod serializer_error;
+mod shared_writer;
+mod span;
 mod tag_directive;
+mod tag_resolver;
+mod tag_resolver_error;
+mod token;
+mod validation_error;
 mod version_directive;
 
 use unsafe_libyaml as sys;
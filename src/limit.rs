@@ -0,0 +1,64 @@
+use std::fmt;
+
+/// Resource limit of a [`Parser`], as configured with [`ParserBuilder`], or
+/// of an [`AliasExpander`] or a [`Deserializer`].
+///
+/// Parser limits are checked for every event, so they cover [`Parser::parse`]
+/// and everything built on it.  [`Parser::load`] and [`Scanner`] only check
+/// [`InputBytes`].
+///
+/// [`Parser`]: struct.Parser.html
+/// [`Parser::parse`]: struct.Parser.html#method.parse
+/// [`Parser::load`]: struct.Parser.html#method.load
+/// [`Scanner`]: struct.Scanner.html
+/// [`InputBytes`]: #variant.InputBytes
+/// [`ParserBuilder`]: struct.ParserBuilder.html
+/// [`AliasExpander`]: struct.AliasExpander.html
+/// [`Deserializer`]: struct.Deserializer.html
+#[derive(Copy, Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum Limit {
+    /// Maximum nesting depth of sequences and mappings.
+    Depth,
+
+    /// Maximum length of a scalar value in bytes.  Checked once LibYAML has
+    /// read the whole scalar, so this does not bound the memory used while
+    /// parsing; [`InputBytes`] does.
+    ///
+    /// [`InputBytes`]: #variant.InputBytes
+    ScalarLength,
+
+    /// Maximum number of events in the stream.
+    Events,
+
+    /// Maximum number of documents in the stream.
+    Documents,
+
+    /// Maximum number of anchored nodes in the stream.
+    Anchors,
+
+    /// Maximum size of the input in bytes, before decoding: the bytes taken
+    /// from a reader, or the length of a slice or string, which is checked
+    /// before parsing starts.
+    InputBytes,
+
+    /// Maximum number of events produced by alias expansion.
+    ExpandedEvents,
+
+    /// Maximum ratio of produced events to parsed events in alias expansion.
+    ExpansionRatio,
+}
+
+impl fmt::Display for Limit {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Depth => write!(f, "nesting depth"),
+            Self::ScalarLength => write!(f, "scalar length"),
+            Self::Events => write!(f, "event count"),
+            Self::Documents => write!(f, "document count"),
+            Self::Anchors => write!(f, "anchor count"),
+            Self::InputBytes => write!(f, "input size"),
+            Self::ExpandedEvents => write!(f, "expanded event count"),
+            Self::ExpansionRatio => write!(f, "alias expansion ratio"),
+        }
+    }
+}
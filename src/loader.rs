@@ -0,0 +1,194 @@
+use std::iter::FusedIterator;
+
+use crate::{Event, Mark, Node, ParserError, Span};
+
+/// Iterator adapter that builds a [`Node`] tree for every document of a stream.
+///
+/// The loader consumes events paired with their spans, as produced by
+/// [`ParserSpannedIter`], and yields the root node of each document.  Aliases
+/// are kept as [`Node::Alias`] and not resolved.  Events that end in the
+/// middle of a document produce [`ParserError::UnexpectedEnd`].  The iterator
+/// is fused, and produces `None` forever after the end of stream or after a
+/// first encountered error.
+///
+/// ```
+/// # use libyaml::*;
+/// #
+/// # fn doctest() -> Result<(), ParserError> {
+/// let parser = Parser::new(&b"--- a\n--- [b, c]\n"[..])?;
+/// let documents = Loader::new(parser.into_spanned_iter()).collect::<Result<Vec<_>, _>>()?;
+/// assert_eq!(documents.len(), 2);
+///
+/// let events = Parser::from_str("[b, c]")?.into_spanned_iter().take(4);
+/// let result = Loader::new(events).next();
+/// assert!(matches!(result, Some(Err(ParserError::UnexpectedEnd { .. }))));
+/// # Ok(())
+/// # }
+/// # doctest().unwrap();
+/// ```
+///
+/// [`Node`]: enum.Node.html
+/// [`Node::Alias`]: enum.Node.html#variant.Alias
+/// [`ParserSpannedIter`]: struct.ParserSpannedIter.html
+/// [`ParserError::UnexpectedEnd`]: enum.ParserError.html#variant.UnexpectedEnd
+pub struct Loader<I> {
+    events: I,
+    mark: Mark,
+    fuse_burnt: bool,
+}
+
+impl<I> Loader<I>
+where
+    I: Iterator<Item = Result<(Event, Span), ParserError>>,
+{
+    /// Create a loader consuming the given events.
+    pub fn new(events: I) -> Self {
+        Self { events, mark: Mark::default(), fuse_burnt: false }
+    }
+
+    fn load_document(&mut self) -> Result<Option<Node>, ParserError> {
+        loop {
+            match self.events.next().transpose()? {
+                Some((Event::StreamStart { .. }, span)) => {
+                    self.mark = span.end;
+                },
+                Some((Event::DocumentStart { .. }, span)) => {
+                    self.mark = span.end;
+                    break;
+                },
+                Some((Event::StreamEnd, _)) | None => {
+                    return Ok(None);
+                },
+                Some((event, span)) => {
+                    return Err(ParserError::UnexpectedEvent { event: event.kind(), mark: span.start });
+                },
+            }
+        }
+
+        let root = self.load_node()?;
+
+        match self.next_event()? {
+            (Event::DocumentEnd { .. }, _) => Ok(Some(root)),
+            (event, span) => Err(ParserError::UnexpectedEvent { event: event.kind(), mark: span.start }),
+        }
+    }
+
+    fn load_node(&mut self) -> Result<Node, ParserError> {
+        let mut stack: Vec<(Node, Option<Node>)> = Vec::new();
+
+        loop {
+            let (event, span) = self.next_event()?;
+
+            let node = match event {
+                Event::Scalar { anchor, tag, value, plain_implicit, quoted_implicit, style } => {
+                    Node::Scalar { anchor, tag, value, plain_implicit, quoted_implicit, style, span }
+                },
+                Event::Alias { anchor } => {
+                    Node::Alias { anchor, span }
+                },
+                Event::SequenceStart { anchor, tag, implicit, style } => {
+                    stack.push((Node::Sequence { anchor, tag, implicit, style, items: Vec::new(), span }, None));
+                    continue;
+                },
+                Event::MappingStart { anchor, tag, implicit, style } => {
+                    stack.push((Node::Mapping { anchor, tag, implicit, style, pairs: Vec::new(), span }, None));
+                    continue;
+                },
+                Event::SequenceEnd => {
+                    match stack.pop() {
+                        Some((mut node @ Node::Sequence { .. }, _)) => {
+                            if let Node::Sequence { span: node_span, .. } = &mut node {
+                                node_span.end = span.end;
+                            }
+
+                            node
+                        },
+                        _ => {
+                            return Err(ParserError::UnexpectedEvent { event: event.kind(), mark: span.start });
+                        },
+                    }
+                },
+                Event::MappingEnd => {
+                    match stack.pop() {
+                        Some((mut node @ Node::Mapping { .. }, None)) => {
+                            if let Node::Mapping { span: node_span, .. } = &mut node {
+                                node_span.end = span.end;
+                            }
+
+                            node
+                        },
+                        _ => {
+                            return Err(ParserError::UnexpectedEvent { event: event.kind(), mark: span.start });
+                        },
+                    }
+                },
+                _ => {
+                    return Err(ParserError::UnexpectedEvent { event: event.kind(), mark: span.start });
+                },
+            };
+
+            match stack.last_mut() {
+                Some((Node::Sequence { items, .. }, _)) => {
+                    items.push(node);
+                },
+                Some((Node::Mapping { pairs, .. }, pending_key)) => {
+                    match pending_key.take() {
+                        Some(key) => pairs.push((key, node)),
+                        None => *pending_key = Some(node),
+                    }
+                },
+                Some(_) => {
+                    unreachable!();
+                },
+                None => {
+                    return Ok(node);
+                },
+            }
+        }
+    }
+
+    fn next_event(&mut self) -> Result<(Event, Span), ParserError> {
+        match self.events.next().transpose()? {
+            Some((event, span)) => {
+                self.mark = span.end;
+                Ok((event, span))
+            },
+            None => {
+                Err(ParserError::UnexpectedEnd { mark: self.mark })
+            },
+        }
+    }
+}
+
+impl<I> Iterator for Loader<I>
+where
+    I: Iterator<Item = Result<(Event, Span), ParserError>>,
+{
+    type Item = Result<Node, ParserError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.fuse_burnt {
+            None
+        } else {
+            match self.load_document() {
+                Ok(Some(node)) => {
+                    Some(Ok(node))
+                },
+                Ok(None) => {
+                    self.fuse_burnt = true;
+                    None
+                },
+                Err(e) => {
+                    self.fuse_burnt = true;
+                    Some(Err(e))
+                },
+            }
+        }
+    }
+}
+
+impl<I> FusedIterator for Loader<I>
+where
+    I: Iterator<Item = Result<(Event, Span), ParserError>>,
+{
+}
@@ -0,0 +1,38 @@
+use std::fmt;
+
+use crate::sys;
+
+/// Position in a YAML stream.
+///
+/// All fields are zero-based; the [`Display`] implementation shows one-based
+/// line and column numbers, as conventionally expected in messages.
+///
+/// [`Display`]: https://doc.rust-lang.org/std/fmt/trait.Display.html
+#[derive(Copy, Clone, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct Mark {
+    /// Byte offset from the start of the stream.
+    pub index: usize,
+
+    /// Line number.
+    pub line: usize,
+
+    /// Column number.
+    pub column: usize,
+}
+
+impl Mark {
+    /// Convert from `yaml_mark_t`.
+    pub fn from_raw(raw: sys::yaml_mark_t) -> Self {
+        Self {
+            index: raw.index as _,
+            line: raw.line as _,
+            column: raw.column as _,
+        }
+    }
+}
+
+impl fmt::Display for Mark {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "line {} column {}", self.line + 1, self.column + 1)
+    }
+}
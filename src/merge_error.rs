@@ -0,0 +1,54 @@
+use std::error;
+use std::fmt;
+
+use crate::Mark;
+
+/// Error returned from [`Node::merge_keys`] and [`Node::merge_keys_strict`].
+///
+/// [`Node::merge_keys`]: enum.Node.html#method.merge_keys
+/// [`Node::merge_keys_strict`]: enum.Node.html#method.merge_keys_strict
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub enum MergeError {
+    /// Merge source is an alias; aliases must be expanded before merging.
+    UnresolvedAlias {
+        /// Target anchor name.
+        anchor: String,
+
+        /// Position of the alias.
+        mark: Mark,
+    },
+
+    /// Merge source is neither a mapping nor a sequence of mappings.
+    InvalidSource {
+        /// Position of the merge source.
+        mark: Mark,
+    },
+
+    /// Mapping contains the same key twice.
+    DuplicateKey {
+        /// Position of the first occurrence of the key.
+        first: Mark,
+
+        /// Position of the second occurrence of the key.
+        second: Mark,
+    },
+}
+
+impl fmt::Display for MergeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::UnresolvedAlias { anchor, mark } => {
+                write!(f, "unresolved alias `{}` as merge source at {}", anchor, mark)
+            },
+            Self::InvalidSource { mark } => {
+                write!(f, "merge source is not a mapping at {}", mark)
+            },
+            Self::DuplicateKey { first, second } => {
+                write!(f, "duplicate mapping key at {}, first defined at {}", second, first)
+            },
+        }
+    }
+}
+
+impl error::Error for MergeError {
+}
@@ -0,0 +1,461 @@
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::iter;
+use std::vec;
+
+use crate::{Event, EventKind, Loader, MappingStyle, Mark, MergeError, ParserError, ScalarStyle};
+use crate::{SequenceStyle, Span};
+use crate::key::Key;
+use crate::tag;
+
+/// Owned YAML node.
+///
+/// Node trees are produced from parser events by [`Loader`] and turned back
+/// into events by [`Dumper`].
+///
+/// [`Loader`]: struct.Loader.html
+/// [`Dumper`]: struct.Dumper.html
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub enum Node {
+    /// A scalar node.
+    Scalar {
+        /// Optional anchor name.
+        anchor: Option<String>,
+
+        /// Optional tag name.
+        tag: Option<String>,
+
+        /// Scalar value.
+        value: String,
+
+        /// If true, no tag will be emitted for the plain style.
+        plain_implicit: bool,
+
+        /// If true, no tag will be emitted for the non-plain styles.
+        quoted_implicit: bool,
+
+        /// Scalar style; if `None`, LibYAML will choose a style.
+        style: Option<ScalarStyle>,
+
+        /// Range of the stream occupied by the node.
+        span: Span,
+    },
+
+    /// A sequence node.
+    Sequence {
+        /// Optional anchor name.
+        anchor: Option<String>,
+
+        /// Optional tag name.
+        tag: Option<String>,
+
+        /// If true, no tag will be emitted.
+        implicit: bool,
+
+        /// Sequence style; if `None`, LibYAML will choose a style.
+        style: Option<SequenceStyle>,
+
+        /// Sequence items.
+        items: Vec<Node>,
+
+        /// Range of the stream occupied by the node.
+        span: Span,
+    },
+
+    /// A mapping node.
+    Mapping {
+        /// Optional anchor name.
+        anchor: Option<String>,
+
+        /// Optional tag name.
+        tag: Option<String>,
+
+        /// If true, no tag will be emitted.
+        implicit: bool,
+
+        /// Mapping style; if `None`, LibYAML will choose a style.
+        style: Option<MappingStyle>,
+
+        /// Mapping key-value pairs.
+        pairs: Vec<(Node, Node)>,
+
+        /// Range of the stream occupied by the node.
+        span: Span,
+    },
+
+    /// An alias to a previously anchored node.
+    Alias {
+        /// Target anchor name.
+        anchor: String,
+
+        /// Range of the stream occupied by the alias.
+        span: Span,
+    },
+}
+
+impl Node {
+    /// Return the anchor name of the node, or the target anchor name of an
+    /// alias.
+    pub fn anchor(&self) -> Option<&str> {
+        match self {
+            Self::Scalar { anchor, .. } => anchor.as_deref(),
+            Self::Sequence { anchor, .. } => anchor.as_deref(),
+            Self::Mapping { anchor, .. } => anchor.as_deref(),
+            Self::Alias { anchor, .. } => Some(anchor),
+        }
+    }
+
+    /// Return the tag name of the node.
+    pub fn tag(&self) -> Option<&str> {
+        match self {
+            Self::Scalar { tag, .. } => tag.as_deref(),
+            Self::Sequence { tag, .. } => tag.as_deref(),
+            Self::Mapping { tag, .. } => tag.as_deref(),
+            Self::Alias { .. } => None,
+        }
+    }
+
+    /// Return the range of the stream occupied by the node.
+    pub fn span(&self) -> Span {
+        match self {
+            Self::Scalar { span, .. } => *span,
+            Self::Sequence { span, .. } => *span,
+            Self::Mapping { span, .. } => *span,
+            Self::Alias { span, .. } => *span,
+        }
+    }
+
+    /// Apply [merge keys] throughout the node tree.
+    ///
+    /// Every `<<` key, either plain and untagged or tagged `!!merge`, is
+    /// replaced in place with the pairs of its value, a mapping or a sequence
+    /// of mappings.  Keys of the merging mapping take precedence over merged
+    /// keys, and earlier merge sources over later ones.  Merge sources that are
+    /// not mappings are left alone.  Aliases must have been expanded, for
+    /// example with [`AliasExpander`].
+    ///
+    /// ```
+    /// # use libyaml::*;
+    /// #
+    /// # fn doctest() -> Result<(), Box<dyn std::error::Error>> {
+    /// let yaml = "base: &base {a: 1, b: 2}\nderived: {<<: *base, b: 3}\n";
+    /// let parser = Parser::from_str(yaml)?;
+    /// let root = Loader::new(AliasExpander::new(parser.into_spanned_iter())).next().unwrap()?;
+    ///
+    /// let derived = match root.merge_keys()? {
+    ///     Node::Mapping { mut pairs, .. } => pairs.remove(1).1,
+    ///     node => panic!("expected a mapping, got {:?}", node),
+    /// };
+    ///
+    /// let pairs = match derived {
+    ///     Node::Mapping { pairs, .. } => pairs,
+    ///     node => panic!("expected a mapping, got {:?}", node),
+    /// };
+    ///
+    /// let values = pairs
+    ///     .iter()
+    ///     .map(|pair| match pair {
+    ///         (Node::Scalar { value: key, .. }, Node::Scalar { value, .. }) => (key.as_str(), value.as_str()),
+    ///         pair => panic!("expected scalars, got {:?}", pair),
+    ///     })
+    ///     .collect::<Vec<_>>();
+    ///
+    /// assert_eq!(values, [("a", "1"), ("b", "3")]);
+    /// # Ok(())
+    /// # }
+    /// # doctest().unwrap();
+    /// ```
+    ///
+    /// [merge keys]: https://yaml.org/type/merge.html
+    /// [`AliasExpander`]: struct.AliasExpander.html
+    pub fn merge_keys(self) -> Result<Self, MergeError> {
+        self.merge_keys_with(false)
+    }
+
+    /// Apply merge keys like [`merge_keys`], but fail on merge sources that
+    /// are not mappings and on mappings containing the same key twice,
+    /// including two merge keys.
+    ///
+    /// ```
+    /// # use libyaml::*;
+    /// #
+    /// # fn doctest() -> Result<(), Box<dyn std::error::Error>> {
+    /// let yaml = "a: &a {x: 1}\nb: &b {y: 2}\nc: {<<: *a, <<: *b}\n";
+    /// let parser = Parser::from_str(yaml)?;
+    /// let root = Loader::new(AliasExpander::new(parser.into_spanned_iter())).next().unwrap()?;
+    /// assert!(matches!(root.merge_keys_strict(), Err(MergeError::DuplicateKey { .. })));
+    /// # Ok(())
+    /// # }
+    /// # doctest().unwrap();
+    /// ```
+    ///
+    /// [`merge_keys`]: #method.merge_keys
+    pub fn merge_keys_strict(self) -> Result<Self, MergeError> {
+        self.merge_keys_with(true)
+    }
+
+    fn merge_keys_with(self, strict: bool) -> Result<Self, MergeError> {
+        // Collections are rebuilt on an explicit stack, children first, so
+        // that deeply nested trees do not overflow the call stack.
+        #[allow(clippy::large_enum_variant)]
+        enum Frame {
+            Sequence(Node, vec::IntoIter<Node>),
+            Mapping(Node, vec::IntoIter<(Node, Node)>, Option<Node>, Option<Node>),
+        }
+
+        let mut stack = Vec::new();
+        let mut next = Some(self);
+        let mut done = None;
+
+        loop {
+            if let Some(node) = next.take() {
+                match node {
+                    Self::Sequence { anchor, tag, implicit, style, items, span } => {
+                        let node = Self::Sequence { anchor, tag, implicit, style, items: Vec::with_capacity(items.len()), span };
+                        stack.push(Frame::Sequence(node, items.into_iter()));
+                    },
+                    Self::Mapping { anchor, tag, implicit, style, pairs, span } => {
+                        let node = Self::Mapping { anchor, tag, implicit, style, pairs: Vec::with_capacity(pairs.len()), span };
+                        stack.push(Frame::Mapping(node, pairs.into_iter(), None, None));
+                    },
+                    node => {
+                        done = Some(node);
+                    },
+                }
+            }
+
+            if let Some(node) = done.take() {
+                match stack.last_mut() {
+                    Some(Frame::Sequence(Self::Sequence { items, .. }, _)) => {
+                        items.push(node);
+                    },
+                    Some(Frame::Mapping(Self::Mapping { pairs, .. }, _, key, _)) => {
+                        match key.take() {
+                            Some(key) => pairs.push((key, node)),
+                            None => *key = Some(node),
+                        }
+                    },
+                    Some(_) => {
+                        unreachable!();
+                    },
+                    None => {
+                        return Ok(node);
+                    },
+                }
+            }
+
+            match stack.last_mut() {
+                Some(Frame::Sequence(_, items)) => {
+                    next = items.next();
+
+                    if next.is_none() {
+                        if let Some(Frame::Sequence(node, _)) = stack.pop() {
+                            done = Some(node);
+                        }
+                    }
+                },
+                Some(Frame::Mapping(_, pairs, _, pending_value)) => {
+                    match pending_value.take() {
+                        Some(value) => {
+                            next = Some(value);
+                        },
+                        None => {
+                            match pairs.next() {
+                                Some((key, value)) => {
+                                    next = Some(key);
+                                    *pending_value = Some(value);
+                                },
+                                None => {
+                                    if let Some(Frame::Mapping(Self::Mapping { anchor, tag, implicit, style, pairs, span }, ..)) = stack.pop() {
+                                        let pairs = merge_pairs(pairs, strict)?;
+                                        done = Some(Self::Mapping { anchor, tag, implicit, style, pairs, span });
+                                    }
+                                },
+                            }
+                        },
+                    }
+                },
+                None => {
+                    unreachable!();
+                },
+            }
+        }
+    }
+
+    /// Build a node from the sequence of events describing it, such as a
+    /// match produced by [`QueryMatcher`].  The nodes are given default
+    /// spans.
+    ///
+    /// [`QueryMatcher`]: struct.QueryMatcher.html
+    pub fn from_events<I>(events: I) -> Result<Self, ParserError>
+    where
+        I: IntoIterator<Item = Event>,
+    {
+        let document_start = Event::DocumentStart { version: None, tags: Vec::new(), implicit: true };
+        let document_end = Event::DocumentEnd { implicit: true };
+        let events = iter::once(document_start)
+            .chain(events)
+            .chain(iter::once(document_end))
+            .map(|event| Ok((event, Span::default())));
+
+        match Loader::new(events).next() {
+            Some(node) => node,
+            None => Err(ParserError::UnexpectedEvent { event: EventKind::DocumentEnd, mark: Mark::default() }),
+        }
+    }
+
+    /// Convert the node into the sequence of events describing it.
+    pub fn into_events(self) -> Vec<Event> {
+        self.into_spanned_events().into_iter().map(|(event, _)| event).collect()
+    }
+
+    /// Convert the node into the sequence of events describing it, paired
+    /// with their spans.
+    ///
+    /// Collection end events are given an empty span at the end of the
+    /// collection.
+    pub fn into_spanned_events(self) -> Vec<(Event, Span)> {
+        enum Frame {
+            Sequence(vec::IntoIter<Node>, Span),
+            Mapping(vec::IntoIter<(Node, Node)>, Option<Node>, Span),
+        }
+
+        let mut events = Vec::new();
+        let mut stack = Vec::new();
+        let mut next = Some(self);
+
+        loop {
+            if let Some(node) = next.take() {
+                match node {
+                    Self::Scalar { anchor, tag, value, plain_implicit, quoted_implicit, style, span } => {
+                        events.push((Event::Scalar { anchor, tag, value, plain_implicit, quoted_implicit, style }, span));
+                    },
+                    Self::Sequence { anchor, tag, implicit, style, items, span } => {
+                        events.push((Event::SequenceStart { anchor, tag, implicit, style }, span));
+                        stack.push(Frame::Sequence(items.into_iter(), span));
+                    },
+                    Self::Mapping { anchor, tag, implicit, style, pairs, span } => {
+                        events.push((Event::MappingStart { anchor, tag, implicit, style }, span));
+                        stack.push(Frame::Mapping(pairs.into_iter(), None, span));
+                    },
+                    Self::Alias { anchor, span } => {
+                        events.push((Event::Alias { anchor }, span));
+                    },
+                }
+            }
+
+            match stack.last_mut() {
+                Some(Frame::Sequence(items, span)) => {
+                    match items.next() {
+                        Some(item) => {
+                            next = Some(item);
+                        },
+                        None => {
+                            events.push((Event::SequenceEnd, Span { start: span.end, end: span.end }));
+                            stack.pop();
+                        },
+                    }
+                },
+                Some(Frame::Mapping(pairs, pending_value, span)) => {
+                    match pending_value.take() {
+                        Some(value) => {
+                            next = Some(value);
+                        },
+                        None => {
+                            match pairs.next() {
+                                Some((key, value)) => {
+                                    next = Some(key);
+                                    *pending_value = Some(value);
+                                },
+                                None => {
+                                    events.push((Event::MappingEnd, Span { start: span.end, end: span.end }));
+                                    stack.pop();
+                                },
+                            }
+                        },
+                    }
+                },
+                None => {
+                    break;
+                },
+            }
+        }
+
+        events
+    }
+}
+
+fn is_merge_key(node: &Node) -> bool {
+    match node {
+        Node::Scalar { tag: Some(tag), .. } => tag == tag::MERGE,
+        Node::Scalar { tag: None, value, style, .. } => value == "<<" && *style == Some(ScalarStyle::Plain),
+        _ => false,
+    }
+}
+
+fn merge_pairs(pairs: Vec<(Node, Node)>, strict: bool) -> Result<Vec<(Node, Node)>, MergeError> {
+    if strict {
+        let mut merge_keys = pairs.iter().filter(|(key, _)| is_merge_key(key)).map(|(key, _)| key.span().start);
+
+        if let (Some(first), Some(second)) = (merge_keys.next(), merge_keys.next()) {
+            return Err(MergeError::DuplicateKey { first, second });
+        }
+    }
+
+    let mut own_keys = HashMap::new();
+
+    for (key, _) in pairs.iter().filter(|(key, _)| !is_merge_key(key)) {
+        if let Some(first) = own_keys.insert(Key::from_node(key), key.span().start) {
+            if strict {
+                return Err(MergeError::DuplicateKey { first, second: key.span().start });
+            }
+        }
+    }
+
+    let mut seen: HashSet<Key> = own_keys.into_keys().collect();
+    let mut merged = Vec::with_capacity(pairs.len());
+
+    for (key, value) in pairs {
+        if !is_merge_key(&key) {
+            merged.push((key, value));
+            continue;
+        }
+
+        let sources = match value {
+            Node::Mapping { pairs, .. } => {
+                vec![pairs]
+            },
+            Node::Sequence { items, span, .. } if items.iter().all(|item| matches!(item, Node::Mapping { .. } | Node::Alias { .. })) => {
+                let mut sources = Vec::with_capacity(items.len());
+
+                for item in items {
+                    match item {
+                        Node::Mapping { pairs, .. } => sources.push(pairs),
+                        Node::Alias { anchor, span } => return Err(MergeError::UnresolvedAlias { anchor, mark: span.start }),
+                        _ => return Err(MergeError::InvalidSource { mark: span.start }),
+                    }
+                }
+
+                sources
+            },
+            Node::Alias { anchor, span } => {
+                return Err(MergeError::UnresolvedAlias { anchor, mark: span.start });
+            },
+            value if strict => {
+                return Err(MergeError::InvalidSource { mark: value.span().start });
+            },
+            value => {
+                merged.push((key, value));
+                continue;
+            },
+        };
+
+        for (key, value) in sources.into_iter().flatten() {
+            if seen.insert(Key::from_node(&key)) {
+                merged.push((key, value));
+            }
+        }
+    }
+
+    Ok(merged)
+}
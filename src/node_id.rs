@@ -0,0 +1,20 @@
+use std::os::raw;
+
+/// Identifier of a node within a [`Document`].
+///
+/// [`Document`]: struct.Document.html
+#[derive(Copy, Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+#[repr(transparent)]
+pub struct NodeId(raw::c_int);
+
+impl NodeId {
+    /// Convert from a raw LibYAML node index.
+    pub fn from_raw(raw: raw::c_int) -> Self {
+        Self(raw)
+    }
+
+    /// Convert to a raw LibYAML node index.
+    pub fn into_raw(self) -> raw::c_int {
+        self.0
+    }
+}
@@ -0,0 +1,80 @@
+use std::cell::RefCell;
+use std::io;
+use std::ops::{Deref, DerefMut};
+use std::rc::Rc;
+
+use crate::{Emitter, EmitterError};
+use crate::shared_writer::SharedWriter;
+
+/// Emitter owning its writer.
+///
+/// `OwnedEmitter` dereferences to [`Emitter`], so events are emitted the same
+/// way.  Once the stream is finished, [`into_inner`] gives the writer back.
+///
+/// ```
+/// # use libyaml::*;
+/// #
+/// # fn doctest() -> Result<(), EmitterError> {
+/// let mut emitter = OwnedEmitter::new(Vec::new())?;
+/// emitter.emit(Event::StreamStart { encoding: None })?;
+/// emitter.emit(Event::DocumentStart { version: None, tags: vec![], implicit: true })?;
+/// emitter.emit(Event::Scalar {
+///     anchor: None,
+///     tag: None,
+///     value: "hello".into(),
+///     plain_implicit: true,
+///     quoted_implicit: false,
+///     style: None,
+/// })?;
+/// emitter.emit(Event::DocumentEnd { implicit: true })?;
+/// emitter.emit(Event::StreamEnd)?;
+/// assert_eq!(emitter.into_inner()?, b"hello\n");
+/// # Ok(())
+/// # }
+/// # doctest().unwrap();
+/// ```
+///
+/// [`Emitter`]: struct.Emitter.html
+/// [`into_inner`]: #method.into_inner
+pub struct OwnedEmitter<'a, W> {
+    emitter: Box<Emitter<'a>>,
+    writer: Rc<RefCell<W>>,
+}
+
+impl<'a, W: io::Write + 'a> OwnedEmitter<'a, W> {
+    /// Create an emitter with default configuration, taking ownership of the
+    /// writer.
+    pub fn new(writer: W) -> Result<Self, EmitterError> {
+        let writer = Rc::new(RefCell::new(writer));
+        let emitter = Emitter::new(SharedWriter(writer.clone()))?;
+        Ok(Self { emitter, writer })
+    }
+
+    /// Flush the emitter and return the writer.
+    ///
+    /// LibYAML writes out everything on *STREAM-END*; events emitted after
+    /// the last flush of an unfinished stream may be incomplete.
+    pub fn into_inner(mut self) -> Result<W, EmitterError> {
+        self.emitter.flush()?;
+        drop(self.emitter);
+
+        match Rc::try_unwrap(self.writer) {
+            Ok(writer) => Ok(writer.into_inner()),
+            Err(_) => unreachable!("writer shared after the emitter is dropped"),
+        }
+    }
+}
+
+impl<'a, W> Deref for OwnedEmitter<'a, W> {
+    type Target = Emitter<'a>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.emitter
+    }
+}
+
+impl<W> DerefMut for OwnedEmitter<'_, W> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.emitter
+    }
+}
@@ -3,7 +3,7 @@ use std::mem;
 use std::os::raw;
 use std::slice;
 
-use crate::{Event, ParserError, ParserIter};
+use crate::{Document, Event, EventRef, Limit, Mark, ParserError, ParserIter, ParserSpannedIter, Scanner, Span};
 use crate::sys;
 
 /// Parser.
@@ -15,8 +15,23 @@ use crate::sys;
 /// `None` forever after the end of stream or after a first encountered error.
 pub struct Parser<'a> {
     inner: sys::yaml_parser_t,
-    reader: Box<dyn io::Read + 'a>,
+    reader: Option<Box<dyn io::Read + 'a>>,
     reader_error: Option<io::Error>,
+    last_event: Option<sys::yaml_event_t>,
+    pub(crate) strict_utf8: bool,
+    pub(crate) max_depth: Option<usize>,
+    pub(crate) max_scalar_length: Option<usize>,
+    pub(crate) max_events: Option<usize>,
+    pub(crate) max_documents: Option<usize>,
+    pub(crate) max_anchors: Option<usize>,
+    pub(crate) max_input_bytes: Option<usize>,
+    depth: usize,
+    events: usize,
+    documents: usize,
+    anchors: usize,
+    input_bytes: usize,
+    input_limit_hit: bool,
+    last_mark: Mark,
 }
 
 impl<'a> Parser<'a> {
@@ -27,44 +42,301 @@ impl<'a> Parser<'a> {
     ///
     /// [`ParserBuilder`]: struct.ParserBuilder.html
     pub fn new<R: io::Read + 'a>(reader: R) -> Result<Box<Self>, ParserError> {
-        let mut inner = unsafe { mem::MaybeUninit::zeroed().assume_init() };
+        let mut parser = Self::initialize(Some(Box::new(reader)))?;
 
-        if unsafe { sys::yaml_parser_initialize(&mut inner) }.ok {
-            let mut parser = Box::new(Self {
-                inner,
-                reader: Box::new(reader),
-                reader_error: None,
-            });
+        unsafe {
+            sys::yaml_parser_set_input(
+                &mut parser.inner,
+                read_handler,
+                parser.as_mut() as *mut _ as *mut _,
+            );
+        }
 
-            unsafe {
-                sys::yaml_parser_set_input(
-                    &mut parser.inner,
-                    read_handler,
-                    parser.as_mut() as *mut _ as *mut _,
-                );
-            }
+        Ok(parser)
+    }
 
-            Ok(parser)
-        } else {
-            Err(ParserError::LibYamlError)
+    /// Create a parser with default configuration, reading directly from a
+    /// byte slice.
+    ///
+    /// Unlike [`new`], this does not go through a `Read` adapter, but it does
+    /// not avoid copying the input either: LibYAML copies the slice into its
+    /// raw buffer and decodes it from there, and does not expose a way to read
+    /// the slice in place.  The `parse` benchmark shows no measurable gain
+    /// over [`new`].  The slice must outlive the parser.
+    ///
+    /// ```
+    /// # use libyaml::*;
+    /// #
+    /// # fn doctest() -> Result<(), ParserError> {
+    /// let events = Parser::from_slice(b"[a, b]")?.into_iter().collect::<Result<Vec<_>, _>>()?;
+    /// assert_eq!(events.len(), 8);
+    /// # Ok(())
+    /// # }
+    /// # doctest().unwrap();
+    /// ```
+    ///
+    /// [`new`]: #method.new
+    pub fn from_slice(input: &'a [u8]) -> Result<Box<Self>, ParserError> {
+        let mut parser = Self::initialize(None)?;
+        parser.input_bytes = input.len();
+
+        unsafe {
+            sys::yaml_parser_set_input_string(
+                &mut parser.inner,
+                input.as_ptr(),
+                input.len() as _,
+            );
         }
+
+        Ok(parser)
+    }
+
+    /// Create a parser with default configuration, reading directly from a
+    /// string.  See [`from_slice`].
+    ///
+    /// [`from_slice`]: #method.from_slice
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(input: &'a str) -> Result<Box<Self>, ParserError> {
+        Self::from_slice(input.as_bytes())
     }
 
     /// Parse an event.
     pub fn parse(&mut self) -> Result<Event, ParserError> {
-        let mut event = unsafe { mem::MaybeUninit::zeroed().assume_init() };
+        self.parse_spanned().map(|(event, _)| event)
+    }
+
+    /// Parse an event, also returning the range of the stream it occupies.
+    pub fn parse_spanned(&mut self) -> Result<(Event, Span), ParserError> {
+        let event = self.parse_raw()?;
+        let span = Span::from_raw_event(&event);
+
+        if self.strict_utf8 {
+            Ok((Event::from_raw_strict(event)?, span))
+        } else {
+            Ok((Event::from_raw(event)?, span))
+        }
+    }
+
+    /// Parse an event borrowing its strings from the parser.  The event stays
+    /// valid until the next call to a parsing method.
+    pub fn parse_ref(&mut self) -> Result<EventRef<'_>, ParserError> {
+        self.parse_ref_spanned().map(|(event, _)| event)
+    }
+
+    /// Parse an event borrowing its strings from the parser, also returning
+    /// the range of the stream it occupies.
+    pub fn parse_ref_spanned(&mut self) -> Result<(EventRef<'_>, Span), ParserError> {
+        let event = self.parse_raw()?;
+        let event = self.last_event.insert(event);
+        Ok((EventRef::from_raw(event)?, Span::from_raw_event(event)))
+    }
+
+    /// Load a document.  Returns `None` after the end of stream.
+    ///
+    /// Calls to this method must not be mixed with calls to [`parse`] on the
+    /// same parser.
+    ///
+    /// ```
+    /// # use libyaml::*;
+    /// #
+    /// # fn doctest() -> Result<(), Box<dyn std::error::Error>> {
+    /// let mut parser = Parser::from_str("a: 1\n--- [x, y]\n")?;
+    ///
+    /// let first = parser.load()?.unwrap();
+    /// let root = first.node(first.root().unwrap()).unwrap();
+    /// assert!(matches!(root, DocumentNode::Mapping { ref pairs, .. } if pairs.len() == 1));
+    ///
+    /// let second = parser.load()?.unwrap();
+    /// let root = second.node(second.root().unwrap()).unwrap();
+    /// assert!(matches!(root, DocumentNode::Sequence { ref items, .. } if items.len() == 2));
+    ///
+    /// assert!(parser.load()?.is_none());
+    /// # Ok(())
+    /// # }
+    /// # doctest().unwrap();
+    /// ```
+    ///
+    /// [`parse`]: #method.parse
+    pub fn load(&mut self) -> Result<Option<Document>, ParserError> {
+        self.check_input()?;
+
+        let mut document = unsafe { mem::MaybeUninit::zeroed().assume_init() };
+
+        if unsafe { sys::yaml_parser_load(&mut self.inner, &mut document) }.ok {
+            debug_assert!(self.reader_error.is_none());
+            let document = Document::from_raw(document);
+            Ok(document.root().map(|_| document))
+        } else {
+            Err(self.error())
+        }
+    }
+
+    /// Convert a parser into an iterator over events paired with their
+    /// spans.
+    pub fn into_spanned_iter(self: Box<Self>) -> ParserSpannedIter<'a> {
+        ParserSpannedIter::new(self)
+    }
+
+    /// Convert a parser into a [`Scanner`] over the tokens of the stream.
+    ///
+    /// [`Scanner`]: struct.Scanner.html
+    pub fn into_scanner(self: Box<Self>) -> Scanner<'a> {
+        Scanner::new(self)
+    }
+
+    pub(crate) fn scan_raw(&mut self) -> Result<sys::yaml_token_t, ParserError> {
+        self.check_input()?;
+
+        let mut token: sys::yaml_token_t = unsafe { mem::MaybeUninit::zeroed().assume_init() };
+
+        if unsafe { sys::yaml_parser_scan(&mut self.inner, &mut token) }.ok {
+            debug_assert!(self.reader_error.is_none());
+            Ok(token)
+        } else {
+            Err(self.error())
+        }
+    }
+
+    fn parse_raw(&mut self) -> Result<sys::yaml_event_t, ParserError> {
+        self.delete_last_event();
+        self.check_input()?;
+
+        let mut event: sys::yaml_event_t = unsafe { mem::MaybeUninit::zeroed().assume_init() };
 
         if unsafe { sys::yaml_parser_parse(&mut self.inner, &mut event) }.ok {
             debug_assert!(self.reader_error.is_none());
-            Ok(Event::from_raw(event)?)
+
+            match self.check_limits(&event) {
+                Ok(()) => {
+                    Ok(event)
+                },
+                Err(e) => {
+                    unsafe {
+                        sys::yaml_event_delete(&mut event);
+                    }
+
+                    Err(e)
+                },
+            }
+        } else {
+            Err(self.error())
+        }
+    }
+
+    fn check_limits(&mut self, event: &sys::yaml_event_t) -> Result<(), ParserError> {
+        fn check(value: usize, max: Option<usize>, limit: Limit, mark: Mark) -> Result<(), ParserError> {
+            match max {
+                Some(max) if value > max => Err(ParserError::LimitExceeded { limit, mark }),
+                _ => Ok(()),
+            }
+        }
+
+        let start_mark = Mark::from_raw(event.start_mark);
+        let end_mark = Mark::from_raw(event.end_mark);
+        self.last_mark = end_mark;
+
+        self.events += 1;
+        check(self.events, self.max_events, Limit::Events, start_mark)?;
+
+        let anchor = unsafe {
+            match event.type_ {
+                sys::YAML_DOCUMENT_START_EVENT => {
+                    self.documents += 1;
+                    check(self.documents, self.max_documents, Limit::Documents, start_mark)?;
+                    None
+                },
+                sys::YAML_SCALAR_EVENT => {
+                    check(event.data.scalar.length as _, self.max_scalar_length, Limit::ScalarLength, start_mark)?;
+                    Some(event.data.scalar.anchor)
+                },
+                sys::YAML_SEQUENCE_START_EVENT => {
+                    self.depth += 1;
+                    check(self.depth, self.max_depth, Limit::Depth, start_mark)?;
+                    Some(event.data.sequence_start.anchor)
+                },
+                sys::YAML_MAPPING_START_EVENT => {
+                    self.depth += 1;
+                    check(self.depth, self.max_depth, Limit::Depth, start_mark)?;
+                    Some(event.data.mapping_start.anchor)
+                },
+                sys::YAML_SEQUENCE_END_EVENT | sys::YAML_MAPPING_END_EVENT => {
+                    self.depth = self.depth.saturating_sub(1);
+                    None
+                },
+                _ => {
+                    None
+                },
+            }
+        };
+
+        if anchor.is_some_and(|anchor| !anchor.is_null()) {
+            self.anchors += 1;
+            check(self.anchors, self.max_anchors, Limit::Anchors, start_mark)?;
+        }
+
+        Ok(())
+    }
+
+    fn check_input(&self) -> Result<(), ParserError> {
+        // Readers are stopped by `read_handler`; slices are counted whole.
+        match self.max_input_bytes {
+            Some(max) if self.input_bytes > max => {
+                Err(ParserError::LimitExceeded { limit: Limit::InputBytes, mark: self.last_mark })
+            },
+            _ => {
+                Ok(())
+            },
+        }
+    }
+
+    fn error(&mut self) -> ParserError {
+        if self.input_limit_hit {
+            ParserError::LimitExceeded { limit: Limit::InputBytes, mark: self.last_mark }
         } else {
-            match mem::replace(&mut self.reader_error, None) {
-                Some(e) => Err(ParserError::IoError(e)),
-                None => Err(ParserError::LibYamlError),
+            match self.reader_error.take() {
+                Some(e) => ParserError::IoError(e),
+                None => ParserError::from_raw(&self.inner),
+            }
+        }
+    }
+
+    fn delete_last_event(&mut self) {
+        if let Some(mut event) = self.last_event.take() {
+            unsafe {
+                sys::yaml_event_delete(&mut event);
             }
         }
     }
 
+    fn initialize(reader: Option<Box<dyn io::Read + 'a>>) -> Result<Box<Self>, ParserError> {
+        let mut inner = unsafe { mem::MaybeUninit::zeroed().assume_init() };
+
+        if unsafe { sys::yaml_parser_initialize(&mut inner) }.ok {
+            Ok(Box::new(Self {
+                inner,
+                reader,
+                reader_error: None,
+                last_event: None,
+                strict_utf8: false,
+                max_depth: None,
+                max_scalar_length: None,
+                max_events: None,
+                max_documents: None,
+                max_anchors: None,
+                max_input_bytes: None,
+                depth: 0,
+                events: 0,
+                documents: 0,
+                anchors: 0,
+                input_bytes: 0,
+                input_limit_hit: false,
+                last_mark: Mark::default(),
+            }))
+        } else {
+            Err(ParserError::from_raw(&inner))
+        }
+    }
+
     /// Return raw pointer to the underlying `yaml_parser_t`.
     pub fn as_raw_ptr(&mut self) -> *mut sys::yaml_parser_t {
         &mut self.inner
@@ -73,6 +345,8 @@ impl<'a> Parser<'a> {
 
 impl Drop for Parser<'_> {
     fn drop(&mut self) {
+        self.delete_last_event();
+
         unsafe {
             sys::yaml_parser_delete(&mut self.inner);
         }
@@ -95,9 +369,21 @@ unsafe fn read_handler(
     size_read: *mut u64,
 ) -> raw::c_int {
     let parser = &mut *(data as *mut Parser);
+    let reader = parser.reader.as_mut().expect("read handler called without a reader");
+    let mut size = size.min(usize::MAX as _) as usize;
+
+    if let Some(max) = parser.max_input_bytes {
+        size = size.min(max.saturating_sub(parser.input_bytes).saturating_add(1));
+    }
 
-    match parser.reader.read(slice::from_raw_parts_mut(buffer, size.min(usize::MAX as _) as _)) {
+    match reader.read(slice::from_raw_parts_mut(buffer, size)) {
+        Ok(n) if parser.max_input_bytes.is_some_and(|max| parser.input_bytes + n > max) => {
+            *size_read = 0;
+            parser.input_limit_hit = true;
+            0
+        },
         Ok(n) => {
+            parser.input_bytes += n;
             *size_read = n as _;
             parser.reader_error = None;
             1
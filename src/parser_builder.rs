@@ -4,6 +4,15 @@ use crate::{Encoding, Parser, ParserError};
 use crate::sys;
 
 /// Builder for parsers.
+///
+/// Resource limits are checked for every parsed event, and exceeding one
+/// yields [`ParserError::LimitExceeded`].  [`Parser::load`] and [`Scanner`]
+/// only check the input size; see [`Limit`].
+///
+/// [`ParserError::LimitExceeded`]: enum.ParserError.html#variant.LimitExceeded
+/// [`Parser::load`]: struct.Parser.html#method.load
+/// [`Scanner`]: struct.Scanner.html
+/// [`Limit`]: enum.Limit.html
 pub struct ParserBuilder<'a> {
     parser: Box<Parser<'a>>,
 }
@@ -14,6 +23,17 @@ impl<'a> ParserBuilder<'a> {
         Parser::new(reader).map(|parser| Self { parser })
     }
 
+    /// Start building a parser reading directly from a byte slice.
+    pub fn from_slice(input: &'a [u8]) -> Result<Self, ParserError> {
+        Parser::from_slice(input).map(|parser| Self { parser })
+    }
+
+    /// Start building a parser reading directly from a string.
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(input: &'a str) -> Result<Self, ParserError> {
+        Parser::from_str(input).map(|parser| Self { parser })
+    }
+
     /// Finish building a parser.
     pub fn finish(self) -> Box<Parser<'a>> {
         self.parser
@@ -30,4 +50,67 @@ impl<'a> ParserBuilder<'a> {
 
         self
     }
+
+    /// Fail with [`EventError::InvalidUtf8`] when an event string is not valid
+    /// UTF-8, instead of replacing invalid sequences with U+FFFD.  Disabled by
+    /// default.
+    ///
+    /// [`EventError::InvalidUtf8`]: enum.EventError.html#variant.InvalidUtf8
+    pub fn strict_utf8(mut self, strict_utf8: bool) -> Self {
+        self.parser.strict_utf8 = strict_utf8;
+        self
+    }
+
+    /// Set the maximum nesting depth of sequences and mappings.
+    pub fn max_depth(mut self, max_depth: usize) -> Self {
+        self.parser.max_depth = Some(max_depth);
+        self
+    }
+
+    /// Set the maximum length of a scalar value in bytes.  The length is
+    /// checked after the scalar has been read; use [`max_input_bytes`] to
+    /// bound memory.
+    ///
+    /// [`max_input_bytes`]: #method.max_input_bytes
+    pub fn max_scalar_length(mut self, max_scalar_length: usize) -> Self {
+        self.parser.max_scalar_length = Some(max_scalar_length);
+        self
+    }
+
+    /// Set the maximum number of events in the stream.
+    pub fn max_events(mut self, max_events: usize) -> Self {
+        self.parser.max_events = Some(max_events);
+        self
+    }
+
+    /// Set the maximum number of documents in the stream.
+    pub fn max_documents(mut self, max_documents: usize) -> Self {
+        self.parser.max_documents = Some(max_documents);
+        self
+    }
+
+    /// Set the maximum number of anchored nodes in the stream.
+    pub fn max_anchors(mut self, max_anchors: usize) -> Self {
+        self.parser.max_anchors = Some(max_anchors);
+        self
+    }
+
+    /// Set the maximum size of the input in bytes, before decoding.  A reader
+    /// is never asked for more than one byte past this limit, and a longer
+    /// slice or string fails on the first call to a parsing method.
+    ///
+    /// ```
+    /// # use libyaml::*;
+    /// #
+    /// # fn doctest() -> Result<(), ParserError> {
+    /// let mut parser = ParserBuilder::from_str("[a, b]")?.max_input_bytes(4).finish();
+    /// assert!(matches!(parser.parse(), Err(ParserError::LimitExceeded { limit: Limit::InputBytes, .. })));
+    /// # Ok(())
+    /// # }
+    /// # doctest().unwrap();
+    /// ```
+    pub fn max_input_bytes(mut self, max_input_bytes: usize) -> Self {
+        self.parser.max_input_bytes = Some(max_input_bytes);
+        self
+    }
 }
@@ -1,8 +1,11 @@
 use std::error;
+use std::ffi;
 use std::fmt;
 use std::io;
+use std::os::raw;
 
-use crate::EventError;
+use crate::{ErrorKind, EventError, EventKind, Limit, Mark};
+use crate::sys;
 
 /// Error returned from [`Parser`] methods.
 ///
@@ -13,18 +16,159 @@ pub enum ParserError {
     IoError(io::Error),
 
     /// LibYAML error.
-    LibYamlError,
+    LibYamlError {
+        /// Error kind.
+        kind: ErrorKind,
+
+        /// Error description.
+        problem: Option<String>,
+
+        /// Byte offset of the problem; only meaningful for reader errors.
+        problem_offset: usize,
+
+        /// Problematic value; only reported by reader errors.
+        problem_value: Option<i32>,
+
+        /// Position of the problem.
+        problem_mark: Mark,
+
+        /// Description of the enclosing construct.
+        context: Option<String>,
+
+        /// Position of the enclosing construct; `Some` if and only if
+        /// `context` is `Some`.
+        context_mark: Option<Mark>,
+    },
+
+    /// Invalid event produced by LibYAML.
+    EventError(EventError),
+
+    /// Event that is not allowed at this point of the stream.
+    UnexpectedEvent {
+        /// Kind of the offending event.
+        event: EventKind,
+
+        /// Position of the event.
+        mark: Mark,
+    },
+
+    /// Events ended in the middle of a document.
+    UnexpectedEnd {
+        /// End of the last event.
+        mark: Mark,
+    },
+
+    /// Alias to an anchor that is not defined, whose node is not complete, or
+    /// whose node was dropped as a duplicate mapping pair.
+    UnknownAnchor {
+        /// Anchor name.
+        anchor: String,
+
+        /// Position of the alias.
+        mark: Mark,
+    },
+
+    /// Mapping contains the same key twice.
+    DuplicateKey {
+        /// Position of the first occurrence of the key.
+        first: Mark,
+
+        /// Position of the second occurrence of the key.
+        second: Mark,
+    },
+
+    /// Resource limit exceeded.
+    LimitExceeded {
+        /// Exceeded limit.
+        limit: Limit,
+
+        /// Position where the limit was exceeded.  If a reader provides too
+        /// much input, this is the end of the last parsed event; if a slice
+        /// is too long, this is the start of the stream.
+        mark: Mark,
+    },
+}
+
+impl ParserError {
+    /// Extract the error reported by a raw `yaml_parser_t`.
+    pub fn from_raw(raw: &sys::yaml_parser_t) -> Self {
+        fn from_raw_cstr(ptr: *const raw::c_char) -> Option<String> {
+            if ptr.is_null() {
+                None
+            } else {
+                Some(unsafe { ffi::CStr::from_ptr(ptr) }.to_string_lossy().into_owned())
+            }
+        }
+
+        let kind = ErrorKind::from_raw(raw.error).unwrap_or(ErrorKind::Parser);
+        let context = from_raw_cstr(raw.context);
+        let context_mark = context.as_ref().map(|_| Mark::from_raw(raw.context_mark));
+
+        Self::LibYamlError {
+            kind,
+            problem: from_raw_cstr(raw.problem),
+            problem_offset: raw.problem_offset as _,
+            problem_value: if kind == ErrorKind::Reader && raw.problem_value != -1 {
+                Some(raw.problem_value)
+            } else {
+                None
+            },
+            problem_mark: Mark::from_raw(raw.problem_mark),
+            context,
+            context_mark,
+        }
+    }
 }
 
 impl From<EventError> for ParserError {
-    fn from(_: EventError) -> Self {
-        Self::LibYamlError
+    fn from(event_error: EventError) -> Self {
+        Self::EventError(event_error)
     }
 }
 
 impl fmt::Display for ParserError {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "YAML parser error")
+        match self {
+            Self::IoError(io_error) => {
+                write!(f, "I/O error: {}", io_error)
+            },
+            Self::LibYamlError { kind, problem: None, .. } => {
+                write!(f, "YAML {}", kind)
+            },
+            Self::LibYamlError { kind: ErrorKind::Reader, problem: Some(problem), problem_offset, problem_value, .. } => {
+                match problem_value {
+                    Some(value) => write!(f, "{}: #{:X} at byte {}", problem, value, problem_offset),
+                    None => write!(f, "{} at byte {}", problem, problem_offset),
+                }
+            },
+            Self::LibYamlError { problem: Some(problem), problem_mark, context, context_mark, .. } => {
+                write!(f, "{} at {}", problem, problem_mark)?;
+
+                if let (Some(context), Some(context_mark)) = (context, context_mark) {
+                    write!(f, ", {} at {}", context, context_mark)?;
+                }
+
+                Ok(())
+            },
+            Self::EventError(event_error) => {
+                write!(f, "invalid YAML event: {}", event_error)
+            },
+            Self::UnexpectedEvent { event, mark } => {
+                write!(f, "unexpected {} event at {}", event, mark)
+            },
+            Self::UnexpectedEnd { mark } => {
+                write!(f, "unexpected end of events at {}", mark)
+            },
+            Self::UnknownAnchor { anchor, mark } => {
+                write!(f, "unknown anchor `{}` at {}", anchor, mark)
+            },
+            Self::DuplicateKey { first, second } => {
+                write!(f, "duplicate mapping key at {}, first defined at {}", second, first)
+            },
+            Self::LimitExceeded { limit, mark } => {
+                write!(f, "{} limit exceeded at {}", limit, mark)
+            },
+        }
     }
 }
 
@@ -32,7 +176,13 @@ impl error::Error for ParserError {
     fn source(&self) -> Option<&(dyn error::Error + 'static)> {
         match self {
             Self::IoError(io_error) => Some(io_error),
-            Self::LibYamlError => None,
+            Self::LibYamlError { .. } => None,
+            Self::EventError(event_error) => Some(event_error),
+            Self::UnexpectedEvent { .. } => None,
+            Self::UnexpectedEnd { .. } => None,
+            Self::UnknownAnchor { .. } => None,
+            Self::DuplicateKey { .. } => None,
+            Self::LimitExceeded { .. } => None,
         }
     }
 }
@@ -0,0 +1,46 @@
+use std::iter::FusedIterator;
+
+use crate::{Event, Parser, ParserError, Span};
+
+/// Iterator adapter for [`Parser`] that pairs every event with its [`Span`].
+///
+/// Like [`ParserIter`], the iterator is fused, and produces `None` forever
+/// after the end of stream or after a first encountered error.
+///
+/// [`Parser`]: struct.Parser.html
+/// [`ParserIter`]: struct.ParserIter.html
+/// [`Span`]: struct.Span.html
+pub struct ParserSpannedIter<'a> {
+    parser: Box<Parser<'a>>,
+    fuse_burnt: bool,
+}
+
+impl<'a> ParserSpannedIter<'a> {
+    /// Convert a parser into an iterator.
+    pub fn new(parser: Box<Parser<'a>>) -> Self {
+        Self { parser, fuse_burnt: false }
+    }
+}
+
+impl Iterator for ParserSpannedIter<'_> {
+    type Item = Result<(Event, Span), ParserError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.fuse_burnt {
+            None
+        } else {
+            match self.parser.parse_spanned() {
+                ret @ Ok((Event::StreamEnd, _)) | ret @ Err(_) => {
+                    self.fuse_burnt = true;
+                    Some(ret)
+                },
+                ret => {
+                    Some(ret)
+                },
+            }
+        }
+    }
+}
+
+impl FusedIterator for ParserSpannedIter<'_> {
+}
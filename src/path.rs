@@ -0,0 +1,177 @@
+use std::fmt;
+use std::iter::Peekable;
+use std::str::{CharIndices, FromStr};
+
+use crate::{PathError, PathSegment};
+
+/// Location of a node within a document, as a list of mapping keys and
+/// sequence indices leading to it from the root.
+///
+/// Paths are written like `spec.containers[2].name`.  Keys containing `.`,
+/// `[`, `]` or `"`, and empty keys, are written in double quotes, with `"` and
+/// `\` escaped by a backslash.  The empty path refers to the root.
+///
+/// ```
+/// # use libyaml::*;
+/// #
+/// # fn doctest() -> Result<(), PathError> {
+/// let path = "spec.containers[2].\"app.kubernetes.io/name\"".parse::<Path>()?;
+///
+/// assert_eq!(path.segments(), [
+///     PathSegment::Key("spec".into()),
+///     PathSegment::Key("containers".into()),
+///     PathSegment::Index(2),
+///     PathSegment::Key("app.kubernetes.io/name".into()),
+/// ]);
+/// assert_eq!(path.to_string(), "spec.containers[2].\"app.kubernetes.io/name\"");
+/// # Ok(())
+/// # }
+/// # doctest().unwrap();
+/// ```
+#[derive(Clone, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct Path {
+    segments: Vec<PathSegment>,
+}
+
+impl Path {
+    /// Create a path referring to the root.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Return the segments of the path.
+    pub fn segments(&self) -> &[PathSegment] {
+        &self.segments
+    }
+
+    /// Check whether the path refers to the root.
+    pub fn is_empty(&self) -> bool {
+        self.segments.is_empty()
+    }
+
+    /// Append a segment.
+    pub fn push(&mut self, segment: PathSegment) {
+        self.segments.push(segment);
+    }
+
+    /// Remove the last segment and return it, or `None` if the path is empty.
+    pub fn pop(&mut self) -> Option<PathSegment> {
+        self.segments.pop()
+    }
+}
+
+impl From<Vec<PathSegment>> for Path {
+    fn from(segments: Vec<PathSegment>) -> Self {
+        Self { segments }
+    }
+}
+
+impl FromStr for Path {
+    type Err = PathError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut chars = s.char_indices().peekable();
+        let mut segments = Vec::new();
+
+        while let Some(&(position, c)) = chars.peek() {
+            match c {
+                '[' => {
+                    chars.next();
+                    segments.push(PathSegment::Index(parse_index(s, &mut chars)?));
+                },
+                '.' if !segments.is_empty() => {
+                    chars.next();
+                    segments.push(PathSegment::Key(parse_key(&mut chars)?));
+                },
+                _ if segments.is_empty() => {
+                    segments.push(PathSegment::Key(parse_key(&mut chars)?));
+                },
+                _ => {
+                    return Err(PathError::UnexpectedCharacter { character: c, position });
+                },
+            }
+        }
+
+        Ok(Self { segments })
+    }
+}
+
+impl fmt::Display for Path {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (i, segment) in self.segments.iter().enumerate() {
+            if i != 0 && matches!(segment, PathSegment::Key(_)) {
+                f.write_str(".")?;
+            }
+
+            write!(f, "{}", segment)?;
+        }
+
+        Ok(())
+    }
+}
+
+pub(crate) fn parse_index(s: &str, chars: &mut Peekable<CharIndices>) -> Result<usize, PathError> {
+    let start = match chars.peek() {
+        Some(&(position, c)) if c.is_ascii_digit() => position,
+        Some(&(position, character)) => return Err(PathError::UnexpectedCharacter { character, position }),
+        None => return Err(PathError::UnexpectedEnd),
+    };
+
+    let mut end = start;
+
+    while let Some(&(position, c)) = chars.peek() {
+        if !c.is_ascii_digit() {
+            break;
+        }
+
+        chars.next();
+        end = position + 1;
+    }
+
+    match chars.next() {
+        Some((_, ']')) => s[start..end].parse().map_err(|_| PathError::InvalidIndex { position: start }),
+        Some((position, character)) => Err(PathError::UnexpectedCharacter { character, position }),
+        None => Err(PathError::UnexpectedEnd),
+    }
+}
+
+pub(crate) fn parse_key(chars: &mut Peekable<CharIndices>) -> Result<String, PathError> {
+    let mut key = String::new();
+
+    match chars.peek() {
+        Some(&(_, '"')) => {
+            chars.next();
+
+            loop {
+                match chars.next() {
+                    Some((_, '"')) => return Ok(key),
+                    Some((_, '\\')) => key.push(chars.next().ok_or(PathError::UnexpectedEnd)?.1),
+                    Some((_, c)) => key.push(c),
+                    None => return Err(PathError::UnexpectedEnd),
+                }
+            }
+        },
+        Some(_) => {
+            while let Some(&(_, c)) = chars.peek() {
+                if matches!(c, '.' | '[' | ']' | '"') {
+                    break;
+                }
+
+                key.push(c);
+                chars.next();
+            }
+
+            match chars.peek() {
+                Some(&(position, character)) if key.is_empty() => {
+                    Err(PathError::UnexpectedCharacter { character, position })
+                },
+                _ => {
+                    Ok(key)
+                },
+            }
+        },
+        None => {
+            Err(PathError::UnexpectedEnd)
+        },
+    }
+}
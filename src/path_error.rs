@@ -0,0 +1,46 @@
+use std::error;
+use std::fmt;
+
+/// Error returned when parsing a [`Path`] or a [`Query`].
+///
+/// [`Path`]: struct.Path.html
+/// [`Query`]: struct.Query.html
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub enum PathError {
+    /// Character that is not allowed at this point of the path or query.
+    UnexpectedCharacter {
+        /// Offending character.
+        character: char,
+
+        /// Byte offset of the character.
+        position: usize,
+    },
+
+    /// Path or query ends in the middle of a segment.
+    UnexpectedEnd,
+
+    /// Sequence index does not fit in `usize`.
+    InvalidIndex {
+        /// Byte offset of the index.
+        position: usize,
+    },
+}
+
+impl fmt::Display for PathError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::UnexpectedCharacter { character, position } => {
+                write!(f, "unexpected character `{}` at offset {}", character, position)
+            },
+            Self::UnexpectedEnd => {
+                write!(f, "unexpected end of path")
+            },
+            Self::InvalidIndex { position } => {
+                write!(f, "invalid sequence index at offset {}", position)
+            },
+        }
+    }
+}
+
+impl error::Error for PathError {
+}
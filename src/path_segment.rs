@@ -0,0 +1,47 @@
+use std::fmt;
+
+/// Step of a [`Path`] from a node to one of its children.
+///
+/// [`Path`]: struct.Path.html
+#[derive(Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum PathSegment {
+    /// Value of the mapping pair with the given scalar key.
+    Key(String),
+
+    /// Sequence item with the given index.
+    Index(usize),
+
+    /// Value of a mapping pair whose key is not a scalar, such as a
+    /// collection or an alias.  Written `[?]`; paths parsed from text never
+    /// contain it.
+    ComplexKey,
+}
+
+impl fmt::Display for PathSegment {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Key(key) if key.is_empty() || key.contains(['.', '[', ']', '"']) => {
+                f.write_str("\"")?;
+
+                for c in key.chars() {
+                    if c == '"' || c == '\\' {
+                        f.write_str("\\")?;
+                    }
+
+                    write!(f, "{}", c)?;
+                }
+
+                f.write_str("\"")
+            },
+            Self::Key(key) => {
+                f.write_str(key)
+            },
+            Self::Index(index) => {
+                write!(f, "[{}]", index)
+            },
+            Self::ComplexKey => {
+                f.write_str("[?]")
+            },
+        }
+    }
+}
@@ -0,0 +1,195 @@
+use std::iter::FusedIterator;
+
+use crate::{Event, ParserError, Path, PathSegment};
+
+/// Iterator adapter that pairs events with the path of their node.
+///
+/// The tracker consumes events, as produced by [`ParserIter`], and maintains
+/// the current [`Path`] from the starts and ends of collections.  A node
+/// event, and the end event of a collection, come with the path of their
+/// node.  The events of a mapping key come with the path of the mapping, and
+/// the value following a key other than a scalar is reached through
+/// [`PathSegment::ComplexKey`].  Stream and document events come with the
+/// empty path.  The iterator is fused, and produces `None` forever after the
+/// end of stream or after a first encountered error.
+///
+/// ```
+/// # use libyaml::*;
+/// #
+/// # fn doctest() -> Result<(), ParserError> {
+/// let parser = Parser::from_str("spec: {containers: [{name: app}, {name: db}]}")?;
+///
+/// let names = PathTracker::new(parser.into_iter())
+///     .filter_map(|event| match event {
+///         Ok((path, Event::Scalar { value, .. })) if path.to_string().ends_with(".name") => {
+///             Some(Ok(format!("{} = {}", path, value)))
+///         },
+///         Ok(_) => None,
+///         Err(e) => Some(Err(e)),
+///     })
+///     .collect::<Result<Vec<_>, _>>()?;
+///
+/// assert_eq!(names, ["spec.containers[0].name = app", "spec.containers[1].name = db"]);
+/// # Ok(())
+/// # }
+/// # doctest().unwrap();
+/// ```
+///
+/// [`ParserIter`]: struct.ParserIter.html
+/// [`Path`]: struct.Path.html
+/// [`PathSegment::ComplexKey`]: enum.PathSegment.html#variant.ComplexKey
+pub struct PathTracker<I> {
+    events: I,
+    path: Path,
+    frames: Vec<Frame>,
+    key_depth: usize,
+    fuse_burnt: bool,
+}
+
+enum Frame {
+    Sequence(usize),
+    Mapping(Option<PathSegment>),
+}
+
+impl<I> PathTracker<I>
+where
+    I: Iterator<Item = Result<Event, ParserError>>,
+{
+    /// Create a tracker consuming the given events.
+    pub fn new(events: I) -> Self {
+        Self {
+            events,
+            path: Path::new(),
+            frames: Vec::new(),
+            key_depth: 0,
+            fuse_burnt: false,
+        }
+    }
+
+    fn next_event(&mut self) -> Result<Option<(Path, Event)>, ParserError> {
+        let event = match self.events.next().transpose()? {
+            Some(event) => event,
+            None => return Ok(None),
+        };
+
+        if self.key_depth > 0 {
+            match event {
+                Event::SequenceStart { .. } | Event::MappingStart { .. } => {
+                    self.key_depth += 1;
+                },
+                Event::SequenceEnd | Event::MappingEnd => {
+                    self.key_depth -= 1;
+
+                    if self.key_depth == 0 {
+                        self.frames.push(Frame::Mapping(Some(PathSegment::ComplexKey)));
+                    }
+                },
+                _ => {},
+            }
+
+            return Ok(Some((self.path.clone(), event)));
+        }
+
+        match event {
+            Event::Alias { .. } | Event::Scalar { .. } | Event::SequenceStart { .. } | Event::MappingStart { .. } => {
+                let segment = match self.frames.last_mut() {
+                    Some(Frame::Mapping(key @ None)) => {
+                        match &event {
+                            Event::Scalar { value, .. } => {
+                                *key = Some(PathSegment::Key(value.clone()));
+                            },
+                            Event::Alias { .. } => {
+                                *key = Some(PathSegment::ComplexKey);
+                            },
+                            _ => {
+                                self.frames.pop();
+                                self.key_depth = 1;
+                            },
+                        }
+
+                        return Ok(Some((self.path.clone(), event)));
+                    },
+                    Some(Frame::Mapping(key)) => {
+                        key.take()
+                    },
+                    Some(Frame::Sequence(index)) => {
+                        *index += 1;
+                        Some(PathSegment::Index(*index - 1))
+                    },
+                    None => {
+                        None
+                    },
+                };
+
+                let nested = segment.is_some();
+
+                if let Some(segment) = segment {
+                    self.path.push(segment);
+                }
+
+                let path = self.path.clone();
+
+                match event {
+                    Event::SequenceStart { .. } => {
+                        self.frames.push(Frame::Sequence(0));
+                    },
+                    Event::MappingStart { .. } => {
+                        self.frames.push(Frame::Mapping(None));
+                    },
+                    _ if nested => {
+                        self.path.pop();
+                    },
+                    _ => {},
+                }
+
+                Ok(Some((path, event)))
+            },
+            Event::SequenceEnd | Event::MappingEnd => {
+                self.frames.pop();
+                let path = self.path.clone();
+
+                if !self.frames.is_empty() {
+                    self.path.pop();
+                }
+
+                Ok(Some((path, event)))
+            },
+            event => {
+                Ok(Some((self.path.clone(), event)))
+            },
+        }
+    }
+}
+
+impl<I> Iterator for PathTracker<I>
+where
+    I: Iterator<Item = Result<Event, ParserError>>,
+{
+    type Item = Result<(Path, Event), ParserError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.fuse_burnt {
+            None
+        } else {
+            match self.next_event() {
+                Ok(Some(event)) => {
+                    Some(Ok(event))
+                },
+                Ok(None) => {
+                    self.fuse_burnt = true;
+                    None
+                },
+                Err(e) => {
+                    self.fuse_burnt = true;
+                    Some(Err(e))
+                },
+            }
+        }
+    }
+}
+
+impl<I> FusedIterator for PathTracker<I>
+where
+    I: Iterator<Item = Result<Event, ParserError>>,
+{
+}
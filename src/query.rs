@@ -0,0 +1,195 @@
+use std::fmt;
+use std::iter::Peekable;
+use std::str::{CharIndices, FromStr};
+
+use crate::{Path, PathError, PathSegment, QuerySegment};
+use crate::path::{parse_index, parse_key};
+
+/// Path expression selecting nodes within a document.
+///
+/// Queries extend the syntax of [`Path`] with wildcards and recursive
+/// descent: `items[*].metadata.name` selects the `name` of the `metadata` of
+/// every item, and `..image` selects every `image` value at any depth.  A
+/// wildcard is written `*` in place of a key or `[*]` in place of an index,
+/// and matches any mapping value or sequence item.  `..` matches any number
+/// of levels, including none; a key following it needs no further dot.
+/// Queries are matched against a stream of events by [`QueryMatcher`].
+///
+/// ```
+/// # use libyaml::*;
+/// #
+/// # fn doctest() -> Result<(), PathError> {
+/// let query = "items[*]..name".parse::<Query>()?;
+///
+/// assert!(query.matches(&"items[0].metadata.name".parse()?));
+/// assert!(query.matches(&"items[3].name".parse()?));
+/// assert!(!query.matches(&"items.name".parse()?));
+/// # Ok(())
+/// # }
+/// # doctest().unwrap();
+/// ```
+///
+/// [`Path`]: struct.Path.html
+/// [`QueryMatcher`]: struct.QueryMatcher.html
+#[derive(Clone, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct Query {
+    segments: Vec<QuerySegment>,
+}
+
+impl Query {
+    /// Return the segments of the query.
+    pub fn segments(&self) -> &[QuerySegment] {
+        &self.segments
+    }
+
+    /// Check whether the query selects the node at the given path.
+    pub fn matches(&self, path: &Path) -> bool {
+        let mut states = self.start();
+
+        for segment in path.segments() {
+            states = self.step(&states, segment);
+        }
+
+        self.is_match(&states)
+    }
+
+    /// Return the set of matched prefix lengths for the root.
+    pub(crate) fn start(&self) -> Vec<usize> {
+        self.closure(vec![0])
+    }
+
+    /// Return the set of matched prefix lengths for a child reached through
+    /// the segment.  An empty set means no descendant can match.
+    pub(crate) fn step(&self, states: &[usize], segment: &PathSegment) -> Vec<usize> {
+        let mut next = Vec::new();
+
+        for &state in states {
+            match self.segments.get(state) {
+                Some(QuerySegment::Descendants) => {
+                    next.push(state);
+                },
+                Some(query_segment) if query_segment.matches(segment) => {
+                    next.push(state + 1);
+                },
+                _ => {},
+            }
+        }
+
+        self.closure(next)
+    }
+
+    pub(crate) fn is_match(&self, states: &[usize]) -> bool {
+        states.contains(&self.segments.len())
+    }
+
+    fn closure(&self, mut states: Vec<usize>) -> Vec<usize> {
+        let mut i = 0;
+
+        while i < states.len() {
+            if let Some(QuerySegment::Descendants) = self.segments.get(states[i]) {
+                states.push(states[i] + 1);
+            }
+
+            i += 1;
+        }
+
+        states.sort_unstable();
+        states.dedup();
+        states
+    }
+}
+
+impl From<Vec<QuerySegment>> for Query {
+    fn from(segments: Vec<QuerySegment>) -> Self {
+        Self { segments }
+    }
+}
+
+impl FromStr for Query {
+    type Err = PathError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut chars = s.char_indices().peekable();
+        let mut segments = Vec::new();
+
+        while let Some(&(position, c)) = chars.peek() {
+            match c {
+                '[' => {
+                    chars.next();
+
+                    if let Some(&(_, '*')) = chars.peek() {
+                        chars.next();
+
+                        match chars.next() {
+                            Some((_, ']')) => segments.push(QuerySegment::Wildcard),
+                            Some((position, character)) => return Err(PathError::UnexpectedCharacter { character, position }),
+                            None => return Err(PathError::UnexpectedEnd),
+                        }
+                    } else {
+                        segments.push(QuerySegment::Index(parse_index(s, &mut chars)?));
+                    }
+                },
+                '.' => {
+                    chars.next();
+
+                    match chars.peek() {
+                        Some(&(_, '.')) => {
+                            chars.next();
+                            segments.push(QuerySegment::Descendants);
+
+                            if let Some(&(_, c)) = chars.peek() {
+                                if c != '[' && c != '.' {
+                                    segments.push(parse_member(&mut chars)?);
+                                }
+                            }
+                        },
+                        _ if segments.is_empty() => {
+                            return Err(PathError::UnexpectedCharacter { character: c, position });
+                        },
+                        _ => {
+                            segments.push(parse_member(&mut chars)?);
+                        },
+                    }
+                },
+                _ if segments.is_empty() => {
+                    segments.push(parse_member(&mut chars)?);
+                },
+                _ => {
+                    return Err(PathError::UnexpectedCharacter { character: c, position });
+                },
+            }
+        }
+
+        Ok(Self { segments })
+    }
+}
+
+impl fmt::Display for Query {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut previous = None;
+
+        for segment in &self.segments {
+            let member = matches!(segment, QuerySegment::Key(_) | QuerySegment::Wildcard);
+
+            if member && previous.is_some() && previous != Some(&QuerySegment::Descendants) {
+                f.write_str(".")?;
+            }
+
+            write!(f, "{}", segment)?;
+            previous = Some(segment);
+        }
+
+        Ok(())
+    }
+}
+
+fn parse_member(chars: &mut Peekable<CharIndices>) -> Result<QuerySegment, PathError> {
+    let quoted = matches!(chars.peek(), Some(&(_, '"')));
+    let key = parse_key(chars)?;
+
+    if !quoted && key == "*" {
+        Ok(QuerySegment::Wildcard)
+    } else {
+        Ok(QuerySegment::Key(key))
+    }
+}
@@ -0,0 +1,272 @@
+use std::collections::VecDeque;
+use std::iter::FusedIterator;
+
+use crate::{Event, ParserError, Path, PathSegment, Query};
+
+/// Iterator adapter that extracts the nodes selected by a [`Query`].
+///
+/// The matcher consumes events, as produced by [`ParserIter`], and produces
+/// each matched node as its path and the events describing it, without
+/// building the documents.  Nodes are produced in document order; a matched
+/// node nested within another is also part of the events of the outer one.
+/// Subtrees that cannot contain a match are skipped by counting their depth.
+/// Mapping keys are never matched.  The events of a match can be turned into
+/// a [`Node`] with [`Node::from_events`].  The iterator is fused, and produces
+/// `None` forever after the end of stream or after a first encountered error.
+///
+/// ```
+/// # use libyaml::*;
+/// #
+/// # fn doctest() -> Result<(), Box<dyn std::error::Error>> {
+/// let yaml = "\
+/// items:
+///   - metadata: {name: web, labels: {tier: front}}
+///   - metadata: {name: db}
+/// ---
+/// items:
+///   - metadata: {name: cache}
+/// ";
+///
+/// let query = "items[*].metadata.name".parse::<Query>()?;
+/// let names = QueryMatcher::new(Parser::from_str(yaml)?.into_iter(), query)
+///     .map(|result| result.and_then(|(_, events)| Node::from_events(events)))
+///     .collect::<Result<Vec<_>, _>>()?;
+///
+/// assert_eq!(names.len(), 3);
+/// assert!(matches!(&names[2], Node::Scalar { value, .. } if value == "cache"));
+///
+/// let query = "..metadata[*]".parse::<Query>()?;
+/// let paths = QueryMatcher::new(Parser::from_str(yaml)?.into_iter(), query)
+///     .map(|result| result.map(|(path, _)| path.to_string()))
+///     .collect::<Result<Vec<_>, _>>()?;
+///
+/// assert_eq!(paths, [
+///     "items[0].metadata.name",
+///     "items[0].metadata.labels",
+///     "items[1].metadata.name",
+///     "items[0].metadata.name",
+/// ]);
+/// # Ok(())
+/// # }
+/// # doctest().unwrap();
+/// ```
+///
+/// [`Query`]: struct.Query.html
+/// [`ParserIter`]: struct.ParserIter.html
+/// [`Node`]: enum.Node.html
+/// [`Node::from_events`]: enum.Node.html#method.from_events
+pub struct QueryMatcher<I> {
+    events: I,
+    query: Query,
+    path: Path,
+    frames: Vec<Frame>,
+    skip_depth: usize,
+    key_depth: usize,
+    recordings: Vec<Recording>,
+    finished: Vec<Recording>,
+    ready: VecDeque<(Path, Vec<Event>)>,
+    fuse_burnt: bool,
+}
+
+struct Frame {
+    states: Vec<usize>,
+    kind: FrameKind,
+}
+
+enum FrameKind {
+    Sequence(usize),
+    Mapping(Option<PathSegment>),
+}
+
+struct Recording {
+    order: usize,
+    path: Path,
+    events: Vec<Event>,
+    depth: usize,
+}
+
+impl<I> QueryMatcher<I>
+where
+    I: Iterator<Item = Result<Event, ParserError>>,
+{
+    /// Create a matcher consuming the given events.
+    pub fn new(events: I, query: Query) -> Self {
+        Self {
+            events,
+            query,
+            path: Path::new(),
+            frames: Vec::new(),
+            skip_depth: 0,
+            key_depth: 0,
+            recordings: Vec::new(),
+            finished: Vec::new(),
+            ready: VecDeque::new(),
+            fuse_burnt: false,
+        }
+    }
+
+    fn next_match(&mut self) -> Result<Option<(Path, Vec<Event>)>, ParserError> {
+        loop {
+            if let Some(ready) = self.ready.pop_front() {
+                return Ok(Some(ready));
+            }
+
+            let event = match self.events.next().transpose()? {
+                Some(event) => event,
+                None => return Ok(None),
+            };
+
+            if self.skip_depth > 0 {
+                self.skip_depth = update_depth(self.skip_depth, &event);
+            } else if self.key_depth > 0 {
+                self.key_depth = update_depth(self.key_depth, &event);
+
+                if self.key_depth == 0 {
+                    if let Some(Frame { kind: FrameKind::Mapping(key), .. }) = self.frames.last_mut() {
+                        *key = Some(PathSegment::ComplexKey);
+                    }
+                }
+            } else {
+                match &event {
+                    Event::Alias { .. } | Event::Scalar { .. } | Event::SequenceStart { .. } | Event::MappingStart { .. } => {
+                        self.start_node(&event);
+                    },
+                    Event::SequenceEnd | Event::MappingEnd => {
+                        self.frames.pop();
+
+                        if !self.frames.is_empty() {
+                            self.path.pop();
+                        }
+                    },
+                    _ => {},
+                }
+            }
+
+            self.record(event);
+        }
+    }
+
+    fn start_node(&mut self, event: &Event) {
+        let segment = match self.frames.last_mut() {
+            Some(Frame { kind: FrameKind::Mapping(key @ None), .. }) => {
+                match event {
+                    Event::Scalar { value, .. } => *key = Some(PathSegment::Key(value.clone())),
+                    Event::Alias { .. } => *key = Some(PathSegment::ComplexKey),
+                    _ => self.key_depth = 1,
+                }
+
+                return;
+            },
+            Some(Frame { kind: FrameKind::Mapping(key), .. }) => {
+                key.take()
+            },
+            Some(Frame { kind: FrameKind::Sequence(index), .. }) => {
+                *index += 1;
+                Some(PathSegment::Index(*index - 1))
+            },
+            None => {
+                None
+            },
+        };
+
+        let states = match (&segment, self.frames.last()) {
+            (Some(segment), Some(parent)) => self.query.step(&parent.states, segment),
+            _ => self.query.start(),
+        };
+
+        let nested = segment.is_some();
+
+        if let Some(segment) = segment {
+            self.path.push(segment);
+        }
+
+        if self.query.is_match(&states) {
+            let order = self.recordings.len() + self.finished.len();
+            self.recordings.push(Recording { order, path: self.path.clone(), events: Vec::new(), depth: 0 });
+        }
+
+        let collection = matches!(event, Event::SequenceStart { .. } | Event::MappingStart { .. });
+
+        if collection && !states.is_empty() {
+            let kind = match event {
+                Event::SequenceStart { .. } => FrameKind::Sequence(0),
+                _ => FrameKind::Mapping(None),
+            };
+
+            self.frames.push(Frame { states, kind });
+        } else {
+            if collection {
+                self.skip_depth = 1;
+            }
+
+            if nested {
+                self.path.pop();
+            }
+        }
+    }
+
+    fn record(&mut self, event: Event) {
+        if self.recordings.is_empty() {
+            return;
+        }
+
+        for recording in &mut self.recordings {
+            recording.depth = update_depth(recording.depth, &event);
+            recording.events.push(event.clone());
+        }
+
+        while let Some(recording) = self.recordings.last() {
+            if recording.depth > 0 {
+                break;
+            }
+
+            self.finished.extend(self.recordings.pop());
+        }
+
+        if self.recordings.is_empty() {
+            self.finished.sort_by_key(|recording| recording.order);
+            self.ready.extend(self.finished.drain(..).map(|recording| (recording.path, recording.events)));
+        }
+    }
+}
+
+impl<I> Iterator for QueryMatcher<I>
+where
+    I: Iterator<Item = Result<Event, ParserError>>,
+{
+    type Item = Result<(Path, Vec<Event>), ParserError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.fuse_burnt {
+            None
+        } else {
+            match self.next_match() {
+                Ok(Some(event)) => {
+                    Some(Ok(event))
+                },
+                Ok(None) => {
+                    self.fuse_burnt = true;
+                    None
+                },
+                Err(e) => {
+                    self.fuse_burnt = true;
+                    Some(Err(e))
+                },
+            }
+        }
+    }
+}
+
+impl<I> FusedIterator for QueryMatcher<I>
+where
+    I: Iterator<Item = Result<Event, ParserError>>,
+{
+}
+
+fn update_depth(depth: usize, event: &Event) -> usize {
+    match event {
+        Event::SequenceStart { .. } | Event::MappingStart { .. } => depth + 1,
+        Event::SequenceEnd | Event::MappingEnd => depth - 1,
+        _ => depth,
+    }
+}
@@ -0,0 +1,56 @@
+use std::fmt;
+
+use crate::PathSegment;
+
+/// Step of a [`Query`].
+///
+/// [`Query`]: struct.Query.html
+#[derive(Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum QuerySegment {
+    /// Value of the mapping pair with the given scalar key.
+    Key(String),
+
+    /// Sequence item with the given index.
+    Index(usize),
+
+    /// Any mapping value or sequence item.
+    Wildcard,
+
+    /// Any number of levels, including none.
+    Descendants,
+}
+
+impl QuerySegment {
+    /// Check whether the segment matches a single path segment.  Descendants
+    /// match any segment.
+    pub fn matches(&self, segment: &PathSegment) -> bool {
+        match (self, segment) {
+            (Self::Key(key), PathSegment::Key(other)) => key == other,
+            (Self::Index(index), PathSegment::Index(other)) => index == other,
+            (Self::Key(_), _) | (Self::Index(_), _) => false,
+            (Self::Wildcard, _) | (Self::Descendants, _) => true,
+        }
+    }
+}
+
+impl fmt::Display for QuerySegment {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Key(key) if key == "*" => {
+                f.write_str("\"*\"")
+            },
+            Self::Key(key) => {
+                write!(f, "{}", PathSegment::Key(key.clone()))
+            },
+            Self::Index(index) => {
+                write!(f, "[{}]", index)
+            },
+            Self::Wildcard => {
+                f.write_str("*")
+            },
+            Self::Descendants => {
+                f.write_str("..")
+            },
+        }
+    }
+}
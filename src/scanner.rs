@@ -0,0 +1,77 @@
+use std::iter::FusedIterator;
+
+use crate::{Parser, ParserError, Span, Token};
+
+/// Scanner producing the tokens of a YAML stream.
+///
+/// The scanner reads its input through a [`Parser`], and reports errors the
+/// same way as [`Parser::parse`].  Only the [`ParserBuilder::max_input_bytes`]
+/// limit applies to scanning.  The iterator is fused, and produces `None`
+/// forever after the end of stream or after a first encountered error.
+///
+/// ```
+/// # use libyaml::*;
+/// #
+/// # fn doctest() -> Result<(), ParserError> {
+/// let tokens = Parser::from_str("a: &x 1")?
+///     .into_scanner()
+///     .map(|token| token.map(|(token, _)| token))
+///     .collect::<Result<Vec<_>, _>>()?;
+///
+/// assert_eq!(tokens[1], Token::BlockMappingStart);
+/// assert_eq!(tokens[5], Token::Anchor { value: "x".into() });
+/// # Ok(())
+/// # }
+/// # doctest().unwrap();
+/// ```
+///
+/// [`Parser`]: struct.Parser.html
+/// [`Parser::parse`]: struct.Parser.html#method.parse
+/// [`ParserBuilder::max_input_bytes`]: struct.ParserBuilder.html#method.max_input_bytes
+pub struct Scanner<'a> {
+    parser: Box<Parser<'a>>,
+    fuse_burnt: bool,
+}
+
+impl<'a> Scanner<'a> {
+    /// Convert a parser into a scanner.  The parser must not have been used
+    /// for parsing or loading.
+    pub fn new(parser: Box<Parser<'a>>) -> Self {
+        Self { parser, fuse_burnt: false }
+    }
+
+    /// Scan a token, also returning the range of the stream it occupies.
+    /// Returns `None` after the end of stream.
+    pub fn scan(&mut self) -> Result<Option<(Token, Span)>, ParserError> {
+        let token = self.parser.scan_raw()?;
+        let span = Span::from_raw_token(&token);
+        Ok(Token::from_raw(token).map(|token| (token, span)))
+    }
+}
+
+impl Iterator for Scanner<'_> {
+    type Item = Result<(Token, Span), ParserError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.fuse_burnt {
+            None
+        } else {
+            match self.scan() {
+                Ok(Some(token)) => {
+                    Some(Ok(token))
+                },
+                Ok(None) => {
+                    self.fuse_burnt = true;
+                    None
+                },
+                Err(e) => {
+                    self.fuse_burnt = true;
+                    Some(Err(e))
+                },
+            }
+        }
+    }
+}
+
+impl FusedIterator for Scanner<'_> {
+}
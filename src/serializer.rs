@@ -0,0 +1,449 @@
+use std::io;
+
+use serde::ser::{self, Serialize};
+
+use crate::{Emitter, Event, SerializerError};
+use crate::tag::{self, Schema};
+
+/// Serialize a value as a stream containing one document.
+///
+/// ```
+/// # use serde::Serialize;
+/// #
+/// #[derive(Serialize)]
+/// struct Config {
+///     name: String,
+///     retries: u32,
+/// }
+///
+/// let config = Config { name: "foo".to_string(), retries: 3 };
+/// assert_eq!(libyaml::to_string(&config).unwrap(), "name: foo\nretries: 3\n");
+/// ```
+pub fn to_writer<W: io::Write, T: Serialize + ?Sized>(writer: W, value: &T) -> Result<(), SerializerError> {
+    let mut serializer = Serializer::new(Emitter::new(writer)?);
+    serializer.serialize_document(value)?;
+    serializer.finish()?;
+    Ok(())
+}
+
+/// Serialize a value as a string containing one document.
+pub fn to_string<T: Serialize + ?Sized>(value: &T) -> Result<String, SerializerError> {
+    String::from_utf8(to_vec(value)?).map_err(|e| SerializerError::Message(e.to_string()))
+}
+
+/// Serialize a value as bytes containing one document.
+pub fn to_vec<T: Serialize + ?Sized>(value: &T) -> Result<Vec<u8>, SerializerError> {
+    let mut vec = Vec::new();
+    to_writer(&mut vec, value)?;
+    Ok(vec)
+}
+
+/// Serde serializer producing events for an [`Emitter`].
+///
+/// Emitter options such as indentation and line width are taken from the
+/// emitter, which can be configured with [`EmitterBuilder`].
+///
+/// Strings that would be read back as another type are quoted.  Byte arrays
+/// are written as `!!binary` scalars.  Unit variants are written as plain
+/// scalars, and other enum variants as mappings with a single key, the
+/// variant name.  Map keys that are not strings are written as the
+/// corresponding YAML nodes.
+///
+/// [`Emitter`]: struct.Emitter.html
+/// [`EmitterBuilder`]: struct.EmitterBuilder.html
+pub struct Serializer<'a> {
+    emitter: Box<Emitter<'a>>,
+    started: bool,
+}
+
+impl<'a> Serializer<'a> {
+    /// Create a serializer writing to the given emitter.  No events must have
+    /// been emitted yet.
+    pub fn new(emitter: Box<Emitter<'a>>) -> Self {
+        Self { emitter, started: false }
+    }
+
+    /// Serialize a value as a document with implicit start and end markers.
+    pub fn serialize_document<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), SerializerError> {
+        self.start()?;
+        self.emit(Event::DocumentStart { version: None, tags: Vec::new(), implicit: true })?;
+        value.serialize(&mut *self)?;
+        self.emit(Event::DocumentEnd { implicit: true })
+    }
+
+    /// Finish the stream and return the emitter.
+    pub fn finish(mut self) -> Result<Box<Emitter<'a>>, SerializerError> {
+        self.start()?;
+        self.emit(Event::StreamEnd)?;
+        Ok(self.emitter)
+    }
+
+    fn start(&mut self) -> Result<(), SerializerError> {
+        if !self.started {
+            self.emit(Event::StreamStart { encoding: None })?;
+            self.started = true;
+        }
+
+        Ok(())
+    }
+
+    fn emit(&mut self, event: Event) -> Result<(), SerializerError> {
+        Ok(self.emitter.emit(event)?)
+    }
+
+    fn emit_plain(&mut self, value: String) -> Result<(), SerializerError> {
+        self.emit(Event::Scalar {
+            anchor: None,
+            tag: None,
+            value,
+            plain_implicit: true,
+            quoted_implicit: false,
+            style: None,
+        })
+    }
+
+    fn emit_str(&mut self, value: &str) -> Result<(), SerializerError> {
+        let plain_safe = Schema::Core.resolve(value) == tag::STR;
+
+        self.emit(Event::Scalar {
+            anchor: None,
+            tag: None,
+            value: value.to_string(),
+            plain_implicit: plain_safe,
+            quoted_implicit: true,
+            style: None,
+        })
+    }
+
+    fn emit_mapping_start(&mut self) -> Result<(), SerializerError> {
+        self.emit(Event::MappingStart { anchor: None, tag: None, implicit: true, style: None })
+    }
+
+    fn emit_sequence_start(&mut self) -> Result<(), SerializerError> {
+        self.emit(Event::SequenceStart { anchor: None, tag: None, implicit: true, style: None })
+    }
+}
+
+impl<'b, 'a> ser::Serializer for &'b mut Serializer<'a> {
+    type Ok = ();
+    type Error = SerializerError;
+
+    type SerializeSeq = Compound<'b, 'a>;
+    type SerializeTuple = Compound<'b, 'a>;
+    type SerializeTupleStruct = Compound<'b, 'a>;
+    type SerializeTupleVariant = Compound<'b, 'a>;
+    type SerializeMap = Compound<'b, 'a>;
+    type SerializeStruct = Compound<'b, 'a>;
+    type SerializeStructVariant = Compound<'b, 'a>;
+
+    fn serialize_bool(self, v: bool) -> Result<(), SerializerError> {
+        self.emit_plain(v.to_string())
+    }
+
+    fn serialize_i8(self, v: i8) -> Result<(), SerializerError> {
+        self.emit_plain(v.to_string())
+    }
+
+    fn serialize_i16(self, v: i16) -> Result<(), SerializerError> {
+        self.emit_plain(v.to_string())
+    }
+
+    fn serialize_i32(self, v: i32) -> Result<(), SerializerError> {
+        self.emit_plain(v.to_string())
+    }
+
+    fn serialize_i64(self, v: i64) -> Result<(), SerializerError> {
+        self.emit_plain(v.to_string())
+    }
+
+    fn serialize_i128(self, v: i128) -> Result<(), SerializerError> {
+        self.emit_plain(v.to_string())
+    }
+
+    fn serialize_u8(self, v: u8) -> Result<(), SerializerError> {
+        self.emit_plain(v.to_string())
+    }
+
+    fn serialize_u16(self, v: u16) -> Result<(), SerializerError> {
+        self.emit_plain(v.to_string())
+    }
+
+    fn serialize_u32(self, v: u32) -> Result<(), SerializerError> {
+        self.emit_plain(v.to_string())
+    }
+
+    fn serialize_u64(self, v: u64) -> Result<(), SerializerError> {
+        self.emit_plain(v.to_string())
+    }
+
+    fn serialize_u128(self, v: u128) -> Result<(), SerializerError> {
+        self.emit_plain(v.to_string())
+    }
+
+    fn serialize_f32(self, v: f32) -> Result<(), SerializerError> {
+        self.emit_plain(format_float(v as f64, format!("{:?}", v)))
+    }
+
+    fn serialize_f64(self, v: f64) -> Result<(), SerializerError> {
+        self.emit_plain(format_float(v, format!("{:?}", v)))
+    }
+
+    fn serialize_char(self, v: char) -> Result<(), SerializerError> {
+        self.emit_str(v.encode_utf8(&mut [0; 4]))
+    }
+
+    fn serialize_str(self, v: &str) -> Result<(), SerializerError> {
+        self.emit_str(v)
+    }
+
+    fn serialize_bytes(self, v: &[u8]) -> Result<(), SerializerError> {
+        self.emit(Event::Scalar {
+            anchor: None,
+            tag: Some(tag::BINARY.to_string()),
+            value: encode_base64(v),
+            plain_implicit: false,
+            quoted_implicit: false,
+            style: None,
+        })
+    }
+
+    fn serialize_none(self) -> Result<(), SerializerError> {
+        self.serialize_unit()
+    }
+
+    fn serialize_some<T: Serialize + ?Sized>(self, value: &T) -> Result<(), SerializerError> {
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<(), SerializerError> {
+        self.emit_plain("null".to_string())
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<(), SerializerError> {
+        self.serialize_unit()
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+    ) -> Result<(), SerializerError> {
+        self.emit_str(variant)
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        value: &T,
+    ) -> Result<(), SerializerError> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        value: &T,
+    ) -> Result<(), SerializerError> {
+        self.emit_mapping_start()?;
+        self.emit_str(variant)?;
+        value.serialize(&mut *self)?;
+        self.emit(Event::MappingEnd)
+    }
+
+    fn serialize_seq(self, _len: Option<usize>) -> Result<Compound<'b, 'a>, SerializerError> {
+        self.emit_sequence_start()?;
+        Ok(Compound { ser: self, variant: false })
+    }
+
+    fn serialize_tuple(self, len: usize) -> Result<Compound<'b, 'a>, SerializerError> {
+        self.serialize_seq(Some(len))
+    }
+
+    fn serialize_tuple_struct(self, _name: &'static str, len: usize) -> Result<Compound<'b, 'a>, SerializerError> {
+        self.serialize_seq(Some(len))
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        _len: usize,
+    ) -> Result<Compound<'b, 'a>, SerializerError> {
+        self.emit_mapping_start()?;
+        self.emit_str(variant)?;
+        self.emit_sequence_start()?;
+        Ok(Compound { ser: self, variant: true })
+    }
+
+    fn serialize_map(self, _len: Option<usize>) -> Result<Compound<'b, 'a>, SerializerError> {
+        self.emit_mapping_start()?;
+        Ok(Compound { ser: self, variant: false })
+    }
+
+    fn serialize_struct(self, _name: &'static str, len: usize) -> Result<Compound<'b, 'a>, SerializerError> {
+        self.serialize_map(Some(len))
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        _len: usize,
+    ) -> Result<Compound<'b, 'a>, SerializerError> {
+        self.emit_mapping_start()?;
+        self.emit_str(variant)?;
+        self.emit_mapping_start()?;
+        Ok(Compound { ser: self, variant: true })
+    }
+}
+
+/// Serializer state for compound values.
+#[doc(hidden)]
+pub struct Compound<'b, 'a> {
+    ser: &'b mut Serializer<'a>,
+    variant: bool,
+}
+
+impl Compound<'_, '_> {
+    fn end_with(self, event: Event) -> Result<(), SerializerError> {
+        self.ser.emit(event)?;
+
+        if self.variant {
+            self.ser.emit(Event::MappingEnd)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl ser::SerializeSeq for Compound<'_, '_> {
+    type Ok = ();
+    type Error = SerializerError;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), SerializerError> {
+        value.serialize(&mut *self.ser)
+    }
+
+    fn end(self) -> Result<(), SerializerError> {
+        self.end_with(Event::SequenceEnd)
+    }
+}
+
+impl ser::SerializeTuple for Compound<'_, '_> {
+    type Ok = ();
+    type Error = SerializerError;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), SerializerError> {
+        value.serialize(&mut *self.ser)
+    }
+
+    fn end(self) -> Result<(), SerializerError> {
+        self.end_with(Event::SequenceEnd)
+    }
+}
+
+impl ser::SerializeTupleStruct for Compound<'_, '_> {
+    type Ok = ();
+    type Error = SerializerError;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), SerializerError> {
+        value.serialize(&mut *self.ser)
+    }
+
+    fn end(self) -> Result<(), SerializerError> {
+        self.end_with(Event::SequenceEnd)
+    }
+}
+
+impl ser::SerializeTupleVariant for Compound<'_, '_> {
+    type Ok = ();
+    type Error = SerializerError;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), SerializerError> {
+        value.serialize(&mut *self.ser)
+    }
+
+    fn end(self) -> Result<(), SerializerError> {
+        self.end_with(Event::SequenceEnd)
+    }
+}
+
+impl ser::SerializeMap for Compound<'_, '_> {
+    type Ok = ();
+    type Error = SerializerError;
+
+    fn serialize_key<T: Serialize + ?Sized>(&mut self, key: &T) -> Result<(), SerializerError> {
+        key.serialize(&mut *self.ser)
+    }
+
+    fn serialize_value<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), SerializerError> {
+        value.serialize(&mut *self.ser)
+    }
+
+    fn end(self) -> Result<(), SerializerError> {
+        self.end_with(Event::MappingEnd)
+    }
+}
+
+impl ser::SerializeStruct for Compound<'_, '_> {
+    type Ok = ();
+    type Error = SerializerError;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, key: &'static str, value: &T) -> Result<(), SerializerError> {
+        self.ser.emit_str(key)?;
+        value.serialize(&mut *self.ser)
+    }
+
+    fn end(self) -> Result<(), SerializerError> {
+        self.end_with(Event::MappingEnd)
+    }
+}
+
+impl ser::SerializeStructVariant for Compound<'_, '_> {
+    type Ok = ();
+    type Error = SerializerError;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, key: &'static str, value: &T) -> Result<(), SerializerError> {
+        self.ser.emit_str(key)?;
+        value.serialize(&mut *self.ser)
+    }
+
+    fn end(self) -> Result<(), SerializerError> {
+        self.end_with(Event::MappingEnd)
+    }
+}
+
+fn format_float(v: f64, formatted: String) -> String {
+    if v.is_nan() {
+        ".nan".to_string()
+    } else if v.is_infinite() {
+        if v > 0.0 { ".inf".to_string() } else { "-.inf".to_string() }
+    } else {
+        formatted
+    }
+}
+
+fn encode_base64(bytes: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+    let mut encoded = String::with_capacity(bytes.len().div_ceil(3) * 4);
+
+    for chunk in bytes.chunks(3) {
+        let triple = chunk.iter().enumerate().fold(0u32, |acc, (i, &b)| acc | (b as u32) << (16 - 8 * i));
+
+        for i in 0..4 {
+            if i <= chunk.len() {
+                encoded.push(ALPHABET[(triple >> (18 - 6 * i) & 0x3f) as usize] as char);
+            } else {
+                encoded.push('=');
+            }
+        }
+    }
+
+    encoded
+}
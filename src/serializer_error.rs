@@ -0,0 +1,48 @@
+use std::error;
+use std::fmt;
+
+use serde::ser;
+
+use crate::EmitterError;
+
+/// Error returned from [`Serializer`] methods.
+///
+/// [`Serializer`]: struct.Serializer.html
+#[derive(Debug)]
+pub enum SerializerError {
+    /// Emitter error.
+    EmitterError(EmitterError),
+
+    /// Error reported by a serialized type or by the serializer itself.
+    Message(String),
+}
+
+impl From<EmitterError> for SerializerError {
+    fn from(emitter_error: EmitterError) -> Self {
+        Self::EmitterError(emitter_error)
+    }
+}
+
+impl ser::Error for SerializerError {
+    fn custom<T: fmt::Display>(msg: T) -> Self {
+        Self::Message(msg.to_string())
+    }
+}
+
+impl fmt::Display for SerializerError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::EmitterError(emitter_error) => write!(f, "{}", emitter_error),
+            Self::Message(message) => write!(f, "{}", message),
+        }
+    }
+}
+
+impl error::Error for SerializerError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            Self::EmitterError(emitter_error) => Some(emitter_error),
+            Self::Message(_) => None,
+        }
+    }
+}
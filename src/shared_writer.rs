@@ -0,0 +1,16 @@
+use std::cell::RefCell;
+use std::io;
+use std::rc::Rc;
+
+/// Writer shared between an emitter and its owner.
+pub(crate) struct SharedWriter<W>(pub(crate) Rc<RefCell<W>>);
+
+impl<W: io::Write> io::Write for SharedWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0.borrow_mut().write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.0.borrow_mut().flush()
+    }
+}
@@ -0,0 +1,32 @@
+use crate::Mark;
+use crate::sys;
+
+/// Range of a YAML stream occupied by an event or a token.
+#[derive(Copy, Clone, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct Span {
+    /// Position of the first character.
+    pub start: Mark,
+
+    /// Position right after the last character.
+    pub end: Mark,
+}
+
+impl Span {
+    /// Convert from the `start_mark` and `end_mark` fields of a raw
+    /// `yaml_event_t`.
+    pub fn from_raw_event(raw: &sys::yaml_event_t) -> Self {
+        Self {
+            start: Mark::from_raw(raw.start_mark),
+            end: Mark::from_raw(raw.end_mark),
+        }
+    }
+
+    /// Convert from the `start_mark` and `end_mark` fields of a raw
+    /// `yaml_token_t`.
+    pub fn from_raw_token(raw: &sys::yaml_token_t) -> Self {
+        Self {
+            start: Mark::from_raw(raw.start_mark),
+            end: Mark::from_raw(raw.end_mark),
+        }
+    }
+}
@@ -2,7 +2,14 @@
 //!
 //! The contents of this module are based on the list of language-independent
 //! YAML tags defined under the `yaml.org` domain.  The list is available at
-//! <https://yaml.org/type>.
+//! <https://yaml.org/type>.  [`Schema`] resolves plain scalars without an
+//! explicit tag to one of these tags.
+//!
+//! [`Schema`]: enum.Schema.html
+
+pub use self::schema::Schema;
+
+mod schema;
 
 /// Unordered set of `key: value` pairs without duplicates.
 pub const MAP: &str = "tag:yaml.org,2002:map";
@@ -0,0 +1,372 @@
+use crate::Event;
+use crate::tag;
+
+/// Rules for resolving the tag of plain scalars without an explicit tag.
+///
+/// ```
+/// # use libyaml::tag::{self, Schema};
+/// #
+/// assert_eq!(Schema::Core.resolve("0x1F"), tag::INT);
+/// assert_eq!(Schema::Core.resolve("yes"), tag::STR);
+/// assert_eq!(Schema::Yaml11.resolve("yes"), tag::BOOL);
+/// assert_eq!(Schema::Yaml11.parse_int("1_000"), Some(1000));
+/// ```
+#[derive(Copy, Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum Schema {
+    /// Failsafe schema; every scalar is a string.
+    Failsafe,
+
+    /// JSON schema of YAML 1.2.
+    Json,
+
+    /// Core schema of YAML 1.2.
+    Core,
+
+    /// Type repository of YAML 1.1, including timestamps and merge keys.
+    Yaml11,
+}
+
+impl Schema {
+    /// Resolve the tag of a plain scalar without an explicit tag.  Values
+    /// that match no other type resolve to [`tag::STR`].
+    ///
+    /// [`tag::STR`]: constant.STR.html
+    pub fn resolve(self, value: &str) -> &'static str {
+        if self.is_null(value) {
+            tag::NULL
+        } else if self.parse_bool(value).is_some() {
+            tag::BOOL
+        } else if self.is_int(value) {
+            tag::INT
+        } else if self.is_float(value) {
+            tag::FLOAT
+        } else if self == Self::Yaml11 && is_yaml11_timestamp(value) {
+            tag::TIMESTAMP
+        } else if self == Self::Yaml11 && value == "<<" {
+            tag::MERGE
+        } else if self == Self::Yaml11 && value == "=" {
+            tag::VALUE
+        } else {
+            tag::STR
+        }
+    }
+
+    /// Resolve the tag of a scalar event.  Plain scalars without a tag are
+    /// resolved with [`resolve`], and other scalars with no specific tag are
+    /// strings.  Returns `None` for scalars with a specific tag and for other
+    /// events.
+    ///
+    /// [`resolve`]: #method.resolve
+    pub fn resolve_event(self, event: &Event) -> Option<&'static str> {
+        match event {
+            Event::Scalar { tag: None, value, plain_implicit: true, .. } => Some(self.resolve(value)),
+            Event::Scalar { tag: None, .. } => Some(tag::STR),
+            Event::Scalar { tag: Some(tag), .. } if tag == "!" => Some(tag::STR),
+            _ => None,
+        }
+    }
+
+    /// Check whether a value is a null under this schema.
+    pub fn is_null(self, value: &str) -> bool {
+        match self {
+            Self::Failsafe => false,
+            Self::Json => value == "null",
+            Self::Core | Self::Yaml11 => matches!(value, "" | "~" | "null" | "Null" | "NULL"),
+        }
+    }
+
+    /// Parse a boolean under this schema.
+    pub fn parse_bool(self, value: &str) -> Option<bool> {
+        match (self, value) {
+            (Self::Failsafe, _) => None,
+            (Self::Json, "true") => Some(true),
+            (Self::Json, "false") => Some(false),
+            (Self::Json, _) => None,
+            (_, "true" | "True" | "TRUE") => Some(true),
+            (_, "false" | "False" | "FALSE") => Some(false),
+            (Self::Yaml11, "y" | "Y" | "yes" | "Yes" | "YES" | "on" | "On" | "ON") => Some(true),
+            (Self::Yaml11, "n" | "N" | "no" | "No" | "NO" | "off" | "Off" | "OFF") => Some(false),
+            _ => None,
+        }
+    }
+
+    /// Parse an integer under this schema.  Returns `None` for values that
+    /// are not integers, and for integers that do not fit in an `i128`.
+    pub fn parse_int(self, value: &str) -> Option<i128> {
+        if !self.is_int(value) {
+            return None;
+        }
+
+        match self {
+            Self::Failsafe => None,
+            Self::Json => value.parse().ok(),
+            Self::Core => {
+                if let Some(digits) = value.strip_prefix("0o") {
+                    i128::from_str_radix(digits, 8).ok()
+                } else if let Some(digits) = value.strip_prefix("0x") {
+                    i128::from_str_radix(digits, 16).ok()
+                } else {
+                    value.parse().ok()
+                }
+            },
+            Self::Yaml11 => {
+                let (negative, unsigned) = split_sign(value);
+                let cleaned = unsigned.replace('_', "");
+
+                let magnitude = if let Some(digits) = cleaned.strip_prefix("0b") {
+                    i128::from_str_radix(digits, 2).ok()?
+                } else if let Some(digits) = cleaned.strip_prefix("0x") {
+                    i128::from_str_radix(digits, 16).ok()?
+                } else if cleaned.contains(':') {
+                    parse_sexagesimal(&cleaned)?
+                } else if cleaned.len() > 1 && cleaned.starts_with('0') {
+                    i128::from_str_radix(&cleaned[1..], 8).ok()?
+                } else {
+                    cleaned.parse().ok()?
+                };
+
+                Some(if negative { -magnitude } else { magnitude })
+            },
+        }
+    }
+
+    /// Parse a floating-point number under this schema.
+    pub fn parse_float(self, value: &str) -> Option<f64> {
+        if !self.is_float(value) {
+            return None;
+        }
+
+        let (negative, unsigned) = split_sign(value);
+
+        let magnitude = match unsigned {
+            ".inf" | ".Inf" | ".INF" => f64::INFINITY,
+            ".nan" | ".NaN" | ".NAN" => f64::NAN,
+            _ if self == Self::Yaml11 => {
+                let cleaned = unsigned.replace('_', "");
+
+                match cleaned.rfind(':') {
+                    Some(index) => {
+                        let base = parse_sexagesimal(&cleaned[..index])? as f64;
+                        base * 60.0 + cleaned[index + 1..].parse::<f64>().ok()?
+                    },
+                    None => cleaned.parse().ok()?,
+                }
+            },
+            _ => unsigned.parse().ok()?,
+        };
+
+        Some(if negative { -magnitude } else { magnitude })
+    }
+
+    fn is_int(self, value: &str) -> bool {
+        match self {
+            Self::Failsafe => false,
+            Self::Json => {
+                let unsigned = value.strip_prefix('-').unwrap_or(value);
+                is_decimal(unsigned)
+            },
+            Self::Core => {
+                if let Some(digits) = value.strip_prefix("0o") {
+                    all_nonempty(digits, |b| (b'0'..=b'7').contains(&b))
+                } else if let Some(digits) = value.strip_prefix("0x") {
+                    all_nonempty(digits, |b| b.is_ascii_hexdigit())
+                } else {
+                    let (_, unsigned) = split_sign(value);
+                    all_nonempty(unsigned, |b| b.is_ascii_digit())
+                }
+            },
+            Self::Yaml11 => {
+                let (_, unsigned) = split_sign(value);
+
+                if let Some(digits) = unsigned.strip_prefix("0b") {
+                    all_digits_or_underscores(digits, |b| b == b'0' || b == b'1')
+                } else if let Some(digits) = unsigned.strip_prefix("0x") {
+                    all_digits_or_underscores(digits, |b| b.is_ascii_hexdigit())
+                } else if let Some((head, tail)) = unsigned.split_once(':') {
+                    head.starts_with(|c: char| ('1'..='9').contains(&c))
+                        && all_digits_or_underscores(head, |b| b.is_ascii_digit())
+                        && tail.split(':').all(is_sexagesimal_part)
+                } else if let Some(digits) = unsigned.strip_prefix('0') {
+                    digits.bytes().all(|b| (b'0'..=b'7').contains(&b) || b == b'_')
+                } else {
+                    unsigned.starts_with(|c: char| c.is_ascii_digit())
+                        && unsigned.bytes().all(|b| b.is_ascii_digit() || b == b'_')
+                }
+            },
+        }
+    }
+
+    fn is_float(self, value: &str) -> bool {
+        match self {
+            Self::Failsafe => false,
+            Self::Json => {
+                let unsigned = value.strip_prefix('-').unwrap_or(value);
+                let (mantissa, exponent) = split_exponent(unsigned);
+
+                let mantissa_ok = match mantissa.split_once('.') {
+                    Some((integer, fraction)) => is_decimal(integer) && fraction.bytes().all(|b| b.is_ascii_digit()),
+                    None => is_decimal(mantissa),
+                };
+
+                mantissa_ok && exponent.is_none_or(is_exponent)
+            },
+            Self::Core => {
+                let (_, unsigned) = split_sign(value);
+
+                match unsigned {
+                    ".inf" | ".Inf" | ".INF" => return true,
+                    ".nan" | ".NaN" | ".NAN" => return unsigned.len() == value.len(),
+                    _ => {},
+                }
+
+                let (mantissa, exponent) = split_exponent(unsigned);
+
+                let mantissa_ok = match mantissa.split_once('.') {
+                    Some((integer, fraction)) => {
+                        integer.bytes().all(|b| b.is_ascii_digit())
+                            && fraction.bytes().all(|b| b.is_ascii_digit())
+                            && !(integer.is_empty() && fraction.is_empty())
+                    },
+                    None => all_nonempty(mantissa, |b| b.is_ascii_digit()),
+                };
+
+                mantissa_ok && exponent.is_none_or(is_exponent)
+            },
+            Self::Yaml11 => {
+                let (_, unsigned) = split_sign(value);
+
+                match unsigned {
+                    ".inf" | ".Inf" | ".INF" => return true,
+                    ".nan" | ".NaN" | ".NAN" => return unsigned.len() == value.len(),
+                    _ => {},
+                }
+
+                let (mantissa, fraction) = match unsigned.split_once('.') {
+                    Some(parts) => parts,
+                    None => return false,
+                };
+
+                if let Some((head, tail)) = mantissa.split_once(':') {
+                    head.starts_with(|c: char| c.is_ascii_digit())
+                        && head.bytes().all(|b| b.is_ascii_digit() || b == b'_')
+                        && tail.split(':').all(is_sexagesimal_part)
+                        && fraction.bytes().all(|b| b.is_ascii_digit() || b == b'_')
+                } else {
+                    let (fraction, exponent) = split_exponent(fraction);
+
+                    mantissa.bytes().all(|b| b.is_ascii_digit() || b == b'_')
+                        && (mantissa.is_empty() || mantissa.starts_with(|c: char| c.is_ascii_digit()))
+                        && fraction.bytes().all(|b| b.is_ascii_digit() || b == b'_')
+                        && (mantissa.bytes().chain(fraction.bytes()).any(|b| b.is_ascii_digit()))
+                        && exponent.is_none_or(|e| e.starts_with(['-', '+']) && is_exponent(e))
+                }
+            },
+        }
+    }
+}
+
+fn split_sign(value: &str) -> (bool, &str) {
+    match value.strip_prefix('-') {
+        Some(unsigned) => (true, unsigned),
+        None => (false, value.strip_prefix('+').unwrap_or(value)),
+    }
+}
+
+fn split_exponent(value: &str) -> (&str, Option<&str>) {
+    match value.find(['e', 'E']) {
+        Some(index) => (&value[..index], Some(&value[index + 1..])),
+        None => (value, None),
+    }
+}
+
+fn all_nonempty(value: &str, f: impl Fn(u8) -> bool) -> bool {
+    !value.is_empty() && value.bytes().all(f)
+}
+
+fn all_digits_or_underscores(value: &str, f: impl Fn(u8) -> bool) -> bool {
+    value.bytes().any(&f) && value.bytes().all(|b| b == b'_' || f(b))
+}
+
+fn is_decimal(value: &str) -> bool {
+    value == "0" || (value.starts_with(|c: char| ('1'..='9').contains(&c)) && value.bytes().all(|b| b.is_ascii_digit()))
+}
+
+fn is_exponent(value: &str) -> bool {
+    let digits = value.strip_prefix(['-', '+']).unwrap_or(value);
+    all_nonempty(digits, |b| b.is_ascii_digit())
+}
+
+fn is_sexagesimal_part(value: &str) -> bool {
+    match value.as_bytes() {
+        [digit] => digit.is_ascii_digit(),
+        [tens, digit] => (b'0'..=b'5').contains(tens) && digit.is_ascii_digit(),
+        _ => false,
+    }
+}
+
+fn parse_sexagesimal(value: &str) -> Option<i128> {
+    value.split(':').try_fold(0i128, |acc, part| acc.checked_mul(60)?.checked_add(part.parse().ok()?))
+}
+
+fn is_yaml11_timestamp(value: &str) -> bool {
+    let bytes = value.as_bytes();
+    let date_len = value.find(['T', 't', ' ', '\t']).unwrap_or(value.len());
+    let mut date = value[..date_len].split('-');
+
+    let date_ok = match (date.next(), date.next(), date.next(), date.next()) {
+        (Some(year), Some(month), Some(day), None) => {
+            year.len() == 4
+                && (1..=2).contains(&month.len())
+                && (1..=2).contains(&day.len())
+                && value[..date_len].bytes().all(|b| b.is_ascii_digit() || b == b'-')
+        },
+        _ => false,
+    };
+
+    if !date_ok {
+        return false;
+    }
+
+    if date_len == value.len() {
+        return bytes.len() == 10;
+    }
+
+    let time = match bytes[date_len] {
+        b'T' | b't' => &value[date_len + 1..],
+        _ => value[date_len..].trim_start_matches([' ', '\t']),
+    };
+
+    let zone_index = time.find(['Z', '+', '-', ' ', '\t']).unwrap_or(time.len());
+    let (clock, zone) = time.split_at(zone_index);
+    let (clock, fraction) = clock.split_once('.').unwrap_or((clock, ""));
+    let mut clock = clock.split(':');
+
+    let clock_ok = match (clock.next(), clock.next(), clock.next(), clock.next()) {
+        (Some(hour), Some(minute), Some(second), None) => {
+            all_nonempty(hour, |b| b.is_ascii_digit())
+                && hour.len() <= 2
+                && minute.len() == 2
+                && minute.bytes().all(|b| b.is_ascii_digit())
+                && second.len() == 2
+                && second.bytes().all(|b| b.is_ascii_digit())
+                && fraction.bytes().all(|b| b.is_ascii_digit())
+        },
+        _ => false,
+    };
+
+    let zone = zone.trim_start_matches([' ', '\t']);
+
+    let zone_ok = match zone.strip_prefix(['+', '-']) {
+        _ if zone.is_empty() || zone == "Z" => true,
+        Some(offset) => {
+            let (hour, minute) = offset.split_once(':').unwrap_or((offset, "00"));
+            all_nonempty(hour, |b| b.is_ascii_digit())
+                && hour.len() <= 2
+                && minute.len() == 2
+                && minute.bytes().all(|b| b.is_ascii_digit())
+        },
+        None => false,
+    };
+
+    clock_ok && zone_ok
+}
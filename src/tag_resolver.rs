@@ -0,0 +1,175 @@
+use crate::{TagDirective, TagResolverError};
+
+/// Expansion and compression of tag shorthands.
+///
+/// The resolver knows the tag directives of a document, as found in
+/// [`Event::DocumentStart`], plus the default `!` and `!!` handles unless the
+/// directives override them.  Suffixes are percent-decoded on expansion and
+/// percent-encoded on compression.
+///
+/// ```
+/// # use libyaml::*;
+/// #
+/// # fn doctest() -> Result<(), TagResolverError> {
+/// let resolver = TagResolver::new(&[TagDirective {
+///     handle: "!e!".to_string(),
+///     prefix: "tag:example.com,2000:".to_string(),
+/// }]);
+///
+/// assert_eq!(resolver.expand("!e!a%20b")?, "tag:example.com,2000:a b");
+/// assert_eq!(resolver.expand("!!str")?, tag::STR);
+/// assert_eq!(resolver.compress("tag:example.com,2000:a b"), "!e!a%20b");
+/// assert_eq!(resolver.compress("urn:x"), "!<urn:x>");
+/// # Ok(())
+/// # }
+/// # doctest().unwrap();
+/// ```
+///
+/// [`Event::DocumentStart`]: enum.Event.html#variant.DocumentStart
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct TagResolver {
+    directives: Vec<TagDirective>,
+}
+
+impl TagResolver {
+    /// Create a resolver for the given tag directives.
+    pub fn new(tags: &[TagDirective]) -> Self {
+        let mut directives = tags.to_vec();
+
+        for (handle, prefix) in [("!", "!"), ("!!", DEFAULT_PREFIX)] {
+            if !directives.iter().any(|d| d.handle == handle) {
+                directives.push(TagDirective { handle: handle.to_string(), prefix: prefix.to_string() });
+            }
+        }
+
+        Self { directives }
+    }
+
+    /// Tag directives known to the resolver, including default handles.
+    pub fn directives(&self) -> &[TagDirective] {
+        &self.directives
+    }
+
+    /// Expand a tag shorthand, such as `!!str`, `!handle!suffix` or
+    /// `!<verbatim>`, to a full tag.  The non-specific tag `!` is returned
+    /// unchanged.
+    pub fn expand(&self, shorthand: &str) -> Result<String, TagResolverError> {
+        if shorthand == "!" {
+            return Ok(shorthand.to_string());
+        }
+
+        if let Some(uri) = shorthand.strip_prefix("!<").and_then(|s| s.strip_suffix('>')) {
+            return percent_decode(uri, 2);
+        }
+
+        let rest = match shorthand.strip_prefix('!') {
+            Some(rest) => rest,
+            None => return Err(TagResolverError::InvalidShorthand(shorthand.to_string())),
+        };
+
+        let handle_len = match rest.find('!') {
+            Some(index) => index + 2,
+            None => 1,
+        };
+
+        let (handle, suffix) = shorthand.split_at(handle_len);
+
+        let name = handle.get(1..handle_len - 1).unwrap_or("");
+
+        if suffix.is_empty() || !name.bytes().all(|b| b.is_ascii_alphanumeric() || b == b'-') {
+            return Err(TagResolverError::InvalidShorthand(shorthand.to_string()));
+        }
+
+        match self.directives.iter().find(|d| d.handle == handle) {
+            Some(directive) => Ok(directive.prefix.clone() + &percent_decode(suffix, handle_len)?),
+            None => Err(TagResolverError::UnknownHandle(handle.to_string())),
+        }
+    }
+
+    /// Compress a full tag to the shortest shorthand using a known handle, or
+    /// to a verbatim tag if no handle applies.  The non-specific tag `!` is
+    /// returned unchanged.
+    pub fn compress(&self, tag: &str) -> String {
+        if tag == "!" {
+            return tag.to_string();
+        }
+
+        let directive = self.directives.iter()
+            .filter(|d| tag.len() > d.prefix.len() && tag.starts_with(&d.prefix))
+            .max_by_key(|d| d.prefix.len() as isize - d.handle.len() as isize);
+
+        match directive {
+            Some(directive) => {
+                directive.handle.clone() + &percent_encode(&tag[directive.prefix.len()..], is_tag_char)
+            },
+            None => {
+                format!("!<{}>", percent_encode(tag, is_uri_char))
+            },
+        }
+    }
+}
+
+impl Default for TagResolver {
+    fn default() -> Self {
+        Self::new(&[])
+    }
+}
+
+const DEFAULT_PREFIX: &str = "tag:yaml.org,2002:";
+
+fn is_uri_char(b: u8) -> bool {
+    b.is_ascii_alphanumeric() || b"-#;/?:@&=+$,_.!~*'()[]".contains(&b)
+}
+
+fn is_tag_char(b: u8) -> bool {
+    is_uri_char(b) && !b"!,[]{}".contains(&b)
+}
+
+fn percent_encode(s: &str, allowed: fn(u8) -> bool) -> String {
+    let mut encoded = String::with_capacity(s.len());
+
+    for b in s.bytes() {
+        if allowed(b) {
+            encoded.push(b as char);
+        } else {
+            encoded.push_str(&format!("%{:02X}", b));
+        }
+    }
+
+    encoded
+}
+
+fn percent_decode(s: &str, offset: usize) -> Result<String, TagResolverError> {
+    let bytes = s.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut index = 0;
+    let mut first_escape = None;
+
+    while index < bytes.len() {
+        if bytes[index] == b'%' {
+            let escaped = bytes.get(index + 1..index + 3).and_then(|h| Some(hex_value(h[0])? << 4 | hex_value(h[1])?));
+
+            match escaped {
+                Some(b) => {
+                    first_escape.get_or_insert(index);
+                    decoded.push(b);
+                    index += 3;
+                },
+                _ => {
+                    return Err(TagResolverError::InvalidPercentEncoding { position: offset + index });
+                },
+            }
+        } else {
+            decoded.push(bytes[index]);
+            index += 1;
+        }
+    }
+
+    String::from_utf8(decoded).map_err(|_| {
+        TagResolverError::InvalidPercentEncoding { position: offset + first_escape.unwrap_or(0) }
+    })
+}
+
+fn hex_value(b: u8) -> Option<u8> {
+    (b as char).to_digit(16).map(|d| d as u8)
+}
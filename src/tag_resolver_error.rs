@@ -0,0 +1,41 @@
+use std::error;
+use std::fmt;
+
+/// Error returned from [`TagResolver`] methods.
+///
+/// [`TagResolver`]: struct.TagResolver.html
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub enum TagResolverError {
+    /// Tag shorthand is not of the form `!<uri>`, `!suffix`, `!!suffix` or
+    /// `!handle!suffix`.
+    InvalidShorthand(String),
+
+    /// Tag handle is declared by no tag directive.
+    UnknownHandle(String),
+
+    /// Tag contains a malformed percent-encoded sequence, or percent-encoded
+    /// bytes that are not valid UTF-8.
+    InvalidPercentEncoding {
+        /// Byte offset of the offending `%` within the tag.
+        position: usize,
+    },
+}
+
+impl fmt::Display for TagResolverError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::InvalidShorthand(shorthand) => {
+                write!(f, "invalid tag shorthand `{}`", shorthand)
+            },
+            Self::UnknownHandle(handle) => {
+                write!(f, "undeclared tag handle `{}`", handle)
+            },
+            Self::InvalidPercentEncoding { position } => {
+                write!(f, "invalid percent-encoded sequence at offset {}", position)
+            },
+        }
+    }
+}
+
+impl error::Error for TagResolverError {
+}
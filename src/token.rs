@@ -0,0 +1,176 @@
+use std::ffi;
+use std::os::raw;
+use std::slice;
+
+use crate::{Encoding, ScalarStyle, TagDirective, VersionDirective};
+use crate::sys;
+
+/// Scanner token.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub enum Token {
+    /// A *STREAM-START* token.
+    StreamStart {
+        /// Detected stream encoding.
+        encoding: Option<Encoding>,
+    },
+
+    /// A *STREAM-END* token.
+    StreamEnd,
+
+    /// A *VERSION-DIRECTIVE* token.
+    VersionDirective(VersionDirective),
+
+    /// A *TAG-DIRECTIVE* token.
+    TagDirective(TagDirective),
+
+    /// A *DOCUMENT-START* token, `---`.
+    DocumentStart,
+
+    /// A *DOCUMENT-END* token, `...`.
+    DocumentEnd,
+
+    /// A *BLOCK-SEQUENCE-START* token.
+    BlockSequenceStart,
+
+    /// A *BLOCK-MAPPING-START* token.
+    BlockMappingStart,
+
+    /// A *BLOCK-END* token.
+    BlockEnd,
+
+    /// A *FLOW-SEQUENCE-START* token, `[`.
+    FlowSequenceStart,
+
+    /// A *FLOW-SEQUENCE-END* token, `]`.
+    FlowSequenceEnd,
+
+    /// A *FLOW-MAPPING-START* token, `{`.
+    FlowMappingStart,
+
+    /// A *FLOW-MAPPING-END* token, `}`.
+    FlowMappingEnd,
+
+    /// A *BLOCK-ENTRY* token, `-`.
+    BlockEntry,
+
+    /// A *FLOW-ENTRY* token, `,`.
+    FlowEntry,
+
+    /// A *KEY* token.
+    Key,
+
+    /// A *VALUE* token.
+    Value,
+
+    /// An *ALIAS* token.
+    Alias {
+        /// Target anchor name.
+        value: String,
+    },
+
+    /// An *ANCHOR* token.
+    Anchor {
+        /// Anchor name.
+        value: String,
+    },
+
+    /// A *TAG* token.
+    Tag {
+        /// Tag handle, such as `!!`; empty for a verbatim tag.
+        handle: String,
+
+        /// Tag suffix.
+        suffix: String,
+    },
+
+    /// A *SCALAR* token.
+    Scalar {
+        /// Scalar value.
+        value: String,
+
+        /// Scalar style.
+        style: Option<ScalarStyle>,
+    },
+}
+
+impl Token {
+    /// Take ownership of a raw `yaml_token_t`.  Returns `None` for an empty
+    /// token.  This method frees the allocated memory.
+    pub fn from_raw(mut raw: sys::yaml_token_t) -> Option<Self> {
+        fn from_raw_cstr(ptr: *const raw::c_char) -> String {
+            unsafe { ffi::CStr::from_ptr(ptr) }.to_string_lossy().into_owned()
+        }
+
+        let ret = unsafe {
+            match raw.type_ {
+                sys::YAML_STREAM_START_TOKEN => {
+                    Some(Self::StreamStart {
+                        encoding: Encoding::from_raw(raw.data.stream_start.encoding),
+                    })
+                },
+                sys::YAML_STREAM_END_TOKEN => {
+                    Some(Self::StreamEnd)
+                },
+                sys::YAML_VERSION_DIRECTIVE_TOKEN => {
+                    Some(Self::VersionDirective(VersionDirective(
+                        raw.data.version_directive.major as _,
+                        raw.data.version_directive.minor as _,
+                    )))
+                },
+                sys::YAML_TAG_DIRECTIVE_TOKEN => {
+                    Some(Self::TagDirective(TagDirective {
+                        handle: from_raw_cstr(raw.data.tag_directive.handle as *const _),
+                        prefix: from_raw_cstr(raw.data.tag_directive.prefix as *const _),
+                    }))
+                },
+                sys::YAML_DOCUMENT_START_TOKEN => Some(Self::DocumentStart),
+                sys::YAML_DOCUMENT_END_TOKEN => Some(Self::DocumentEnd),
+                sys::YAML_BLOCK_SEQUENCE_START_TOKEN => Some(Self::BlockSequenceStart),
+                sys::YAML_BLOCK_MAPPING_START_TOKEN => Some(Self::BlockMappingStart),
+                sys::YAML_BLOCK_END_TOKEN => Some(Self::BlockEnd),
+                sys::YAML_FLOW_SEQUENCE_START_TOKEN => Some(Self::FlowSequenceStart),
+                sys::YAML_FLOW_SEQUENCE_END_TOKEN => Some(Self::FlowSequenceEnd),
+                sys::YAML_FLOW_MAPPING_START_TOKEN => Some(Self::FlowMappingStart),
+                sys::YAML_FLOW_MAPPING_END_TOKEN => Some(Self::FlowMappingEnd),
+                sys::YAML_BLOCK_ENTRY_TOKEN => Some(Self::BlockEntry),
+                sys::YAML_FLOW_ENTRY_TOKEN => Some(Self::FlowEntry),
+                sys::YAML_KEY_TOKEN => Some(Self::Key),
+                sys::YAML_VALUE_TOKEN => Some(Self::Value),
+                sys::YAML_ALIAS_TOKEN => {
+                    Some(Self::Alias {
+                        value: from_raw_cstr(raw.data.alias.value as *const _),
+                    })
+                },
+                sys::YAML_ANCHOR_TOKEN => {
+                    Some(Self::Anchor {
+                        value: from_raw_cstr(raw.data.anchor.value as *const _),
+                    })
+                },
+                sys::YAML_TAG_TOKEN => {
+                    Some(Self::Tag {
+                        handle: from_raw_cstr(raw.data.tag.handle as *const _),
+                        suffix: from_raw_cstr(raw.data.tag.suffix as *const _),
+                    })
+                },
+                sys::YAML_SCALAR_TOKEN => {
+                    Some(Self::Scalar {
+                        value: String::from_utf8_lossy(slice::from_raw_parts(
+                            raw.data.scalar.value,
+                            raw.data.scalar.length as _,
+                        )).into_owned(),
+                        style: ScalarStyle::from_raw(raw.data.scalar.style),
+                    })
+                },
+                _ => {
+                    None
+                },
+            }
+        };
+
+        unsafe {
+            sys::yaml_token_delete(&mut raw);
+        }
+
+        ret
+    }
+}
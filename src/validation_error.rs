@@ -0,0 +1,94 @@
+use std::error;
+use std::fmt;
+
+use crate::EventKind;
+
+/// Error returned from [`EventValidator::validate`].
+///
+/// Each variant reports the index of the offending event in the stream,
+/// counting from zero.
+///
+/// [`EventValidator::validate`]: struct.EventValidator.html#method.validate
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub enum ValidationError {
+    /// Event that is not allowed at this point of the stream.
+    UnexpectedEvent {
+        /// Kind of the offending event.
+        event: EventKind,
+
+        /// Kinds of events allowed at this point; empty after the end of
+        /// stream.
+        expected: &'static [EventKind],
+
+        /// Index of the event.
+        index: usize,
+    },
+
+    /// Mapping ends after a key without a value.
+    MissingValue {
+        /// Index of the *MAPPING-END* event.
+        index: usize,
+    },
+
+    /// Alias to an anchor that is not defined earlier in the document.
+    UndefinedAlias {
+        /// Anchor name.
+        anchor: String,
+
+        /// Index of the alias event.
+        index: usize,
+    },
+
+    /// Anchor defined twice in the same document.
+    DuplicateAnchor {
+        /// Anchor name.
+        anchor: String,
+
+        /// Index of the event defining the anchor the second time.
+        index: usize,
+    },
+}
+
+impl fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::UnexpectedEvent { event, expected, index } => {
+                write!(f, "unexpected {} event #{}", event, index)?;
+
+                match expected.split_last() {
+                    Some((last, [])) => {
+                        write!(f, ", expected {}", last)
+                    },
+                    Some((last, rest)) => {
+                        f.write_str(", expected ")?;
+
+                        for (i, kind) in rest.iter().enumerate() {
+                            if i != 0 {
+                                f.write_str(", ")?;
+                            }
+
+                            write!(f, "{}", kind)?;
+                        }
+
+                        write!(f, " or {}", last)
+                    },
+                    None => {
+                        f.write_str(" after end of stream")
+                    },
+                }
+            },
+            Self::MissingValue { index } => {
+                write!(f, "mapping ends without a value for the last key at event #{}", index)
+            },
+            Self::UndefinedAlias { anchor, index } => {
+                write!(f, "alias to undefined anchor `{}` at event #{}", anchor, index)
+            },
+            Self::DuplicateAnchor { anchor, index } => {
+                write!(f, "anchor `{}` defined twice at event #{}", anchor, index)
+            },
+        }
+    }
+}
+
+impl error::Error for ValidationError {
+}